@@ -0,0 +1,302 @@
+//! Validation of block announcements before the announced blocks are downloaded.
+//!
+//! Announced headers must carry an Aura seal by the authority of their slot, optionally
+//! restricted to an allow-list of authors taken from `--announce-author` or the chain spec. The
+//! check only needs the header and the authority set at its parent, so forged announcements are
+//! dropped without fetching or importing the block. Announcements building on an unknown parent
+//! cannot be checked and are ignored; the blocks are still fetched by the regular sync.
+//!
+//! `BlockAnnounceValidator` is not told which peer sent an announcement; restricting peers is
+//! left to the network's reserved peer set.
+
+use node_template_runtime::{
+	opaque::{Block, Header},
+	Hash,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::block_validation::{BlockAnnounceValidator, Validation};
+use sp_consensus_aura::{
+	digests::CompatibleDigestItem,
+	sr25519::{AuthorityId as AuraId, AuthorityPair as AuraPair, AuthoritySignature},
+	AuraApi, Slot,
+};
+use sp_core::Pair;
+use sp_runtime::{generic::BlockId, traits::Header as _};
+use std::{collections::VecDeque, error::Error, future::Future, pin::Pin, sync::Arc};
+use substrate_prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
+
+/// The number of parent blocks whose authority set is kept. Announcements of competing blocks
+/// mostly build on the same few parents.
+const AUTHORITY_CACHE_SIZE: usize = 16;
+
+/// Why an announcement was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rejection {
+	/// The header has no Aura pre-runtime digest or seal.
+	Unsealed,
+	/// The parent block is not known yet, so neither is the authority set.
+	UnknownParent,
+	/// The authority set to check the seal against could not be read.
+	UnknownAuthorities,
+	/// The seal is not a valid signature of the slot's authority.
+	BadSeal,
+	/// The slot's authority is not on the allow-list.
+	AuthorNotAllowed,
+}
+
+impl Rejection {
+	fn label(self) -> &'static str {
+		match self {
+			Self::Unsealed => "unsealed",
+			Self::UnknownParent => "unknown_parent",
+			Self::UnknownAuthorities => "unknown_authorities",
+			Self::BadSeal => "bad_seal",
+			Self::AuthorNotAllowed => "author_not_allowed",
+		}
+	}
+
+	/// Peers relaying forged headers are disconnected; the other rejections may be caused by our
+	/// own state or configuration.
+	fn disconnect(self) -> bool {
+		matches!(self, Self::Unsealed | Self::BadSeal)
+	}
+}
+
+/// Block announcement metrics.
+struct Metrics {
+	rejected: CounterVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			rejected: register(
+				CounterVec::new(
+					Opts::new(
+						"block_announces_rejected_total",
+						"Number of block announcements rejected, by reason",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Accepts announcements of blocks sealed by an allowed Aura authority.
+pub struct AuraAnnounceValidator<C> {
+	client: Arc<C>,
+	allowed_authors: Vec<AuraId>,
+	/// The authority sets at recently seen parent blocks, oldest first.
+	authorities: VecDeque<(Hash, Vec<AuraId>)>,
+	metrics: Option<Metrics>,
+}
+
+impl<C> AuraAnnounceValidator<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AuraApi<Block, AuraId>,
+{
+	/// Create a validator accepting blocks by `allowed_authors`, or by any current authority if
+	/// the list is empty.
+	pub fn new(
+		client: Arc<C>,
+		allowed_authors: Vec<AuraId>,
+		registry: Option<&Registry>,
+	) -> Result<Self, PrometheusError> {
+		let metrics = registry.map(Metrics::register).transpose()?;
+		Ok(Self { client, allowed_authors, authorities: VecDeque::new(), metrics })
+	}
+
+	fn check(&mut self, header: &Header) -> Result<(), Rejection> {
+		let unsealed = unseal(header)?;
+		let authorities = self.authorities_at(*header.parent_hash())?;
+		check_author(&unsealed, authorities, &self.allowed_authors)
+	}
+
+	/// The authority set at `parent`, from the cache if possible.
+	fn authorities_at(&mut self, parent: Hash) -> Result<&[AuraId], Rejection> {
+		let cached = self.authorities.iter().position(|(hash, _)| *hash == parent);
+		let index = match cached {
+			Some(index) => index,
+			None => {
+				if !matches!(self.client.header(BlockId::Hash(parent)), Ok(Some(_))) {
+					return Err(Rejection::UnknownParent)
+				}
+				let authorities = self
+					.client
+					.runtime_api()
+					.authorities(&BlockId::Hash(parent))
+					.map_err(|_| Rejection::UnknownAuthorities)?;
+				if self.authorities.len() == AUTHORITY_CACHE_SIZE {
+					self.authorities.pop_front();
+				}
+				self.authorities.push_back((parent, authorities));
+				self.authorities.len() - 1
+			},
+		};
+		Ok(&self.authorities[index].1)
+	}
+}
+
+/// An announced header split into its slot, the header as it was signed and the seal.
+struct Unsealed {
+	slot: Slot,
+	pre_header: Header,
+	signature: AuthoritySignature,
+}
+
+/// Split the Aura seal off `header`.
+fn unseal(header: &Header) -> Result<Unsealed, Rejection> {
+	let slot = sc_consensus_aura::find_pre_digest::<Block, AuthoritySignature>(header)
+		.map_err(|_| Rejection::Unsealed)?;
+	let mut pre_header = header.clone();
+	let signature = pre_header
+		.digest_mut()
+		.pop()
+		.and_then(|seal| CompatibleDigestItem::<AuthoritySignature>::as_aura_seal(&seal))
+		.ok_or(Rejection::Unsealed)?;
+	Ok(Unsealed { slot, pre_header, signature })
+}
+
+/// Check that the seal is a signature of the authority of the slot in `authorities`, and that the
+/// authority is in `allowed_authors` unless that is empty.
+fn check_author(
+	unsealed: &Unsealed,
+	authorities: &[AuraId],
+	allowed_authors: &[AuraId],
+) -> Result<(), Rejection> {
+	if authorities.is_empty() {
+		return Err(Rejection::UnknownAuthorities)
+	}
+	let author = &authorities[(*unsealed.slot % authorities.len() as u64) as usize];
+
+	if !AuraPair::verify(&unsealed.signature, unsealed.pre_header.hash().as_ref(), author) {
+		return Err(Rejection::BadSeal)
+	}
+	if !allowed_authors.is_empty() && !allowed_authors.contains(author) {
+		return Err(Rejection::AuthorNotAllowed)
+	}
+	Ok(())
+}
+
+impl<C> BlockAnnounceValidator<Block> for AuraAnnounceValidator<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AuraApi<Block, AuraId>,
+{
+	fn validate(
+		&mut self,
+		header: &Header,
+		_data: &[u8],
+	) -> Pin<Box<dyn Future<Output = Result<Validation, Box<dyn Error + Send>>> + Send>> {
+		let validation = match self.check(header) {
+			Ok(()) => Validation::Success { is_new_best: false },
+			Err(rejection) => {
+				if let Some(metrics) = &self.metrics {
+					metrics.rejected.with_label_values(&[rejection.label()]).inc();
+				}
+				Validation::Failure { disconnect: rejection.disconnect() }
+			},
+		};
+
+		Box::pin(async move { Ok(validation) })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::DigestItem;
+
+	fn pair(seed: u8) -> AuraPair {
+		AuraPair::from_seed(&[seed; 32])
+	}
+
+	/// A header of `slot`, sealed by `signer`.
+	fn sealed(slot: u64, signer: &AuraPair) -> Header {
+		let mut header = Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		header.digest_mut().push(
+			<DigestItem as CompatibleDigestItem<AuthoritySignature>>::aura_pre_digest(Slot::from(
+				slot,
+			)),
+		);
+		let signature = signer.sign(header.hash().as_ref());
+		header
+			.digest_mut()
+			.push(<DigestItem as CompatibleDigestItem<AuthoritySignature>>::aura_seal(signature));
+		header
+	}
+
+	fn check(
+		header: &Header,
+		authorities: &[AuraId],
+		allowed_authors: &[AuraId],
+	) -> Result<(), Rejection> {
+		check_author(&unseal(header)?, authorities, allowed_authors)
+	}
+
+	#[test]
+	fn accepts_blocks_sealed_by_the_slot_authority() {
+		let authorities = vec![pair(1).public(), pair(2).public()];
+		assert_eq!(check(&sealed(4, &pair(1)), &authorities, &[]), Ok(()));
+		assert_eq!(check(&sealed(5, &pair(2)), &authorities, &[]), Ok(()));
+	}
+
+	#[test]
+	fn rejects_unsealed_headers() {
+		let mut header = sealed(4, &pair(1));
+		header.digest_mut().pop();
+		assert_eq!(check(&header, &[pair(1).public()], &[]), Err(Rejection::Unsealed));
+
+		header.digest_mut().pop();
+		assert_eq!(check(&header, &[pair(1).public()], &[]), Err(Rejection::Unsealed));
+	}
+
+	#[test]
+	fn rejects_seals_by_another_authority() {
+		let authorities = vec![pair(1).public(), pair(2).public()];
+		assert_eq!(check(&sealed(5, &pair(1)), &authorities, &[]), Err(Rejection::BadSeal));
+		assert_eq!(check(&sealed(4, &pair(3)), &authorities, &[]), Err(Rejection::BadSeal));
+	}
+
+	#[test]
+	fn rejects_headers_changed_after_sealing() {
+		let mut header = sealed(4, &pair(1));
+		header.number = 2;
+		assert_eq!(check(&header, &[pair(1).public()], &[]), Err(Rejection::BadSeal));
+	}
+
+	#[test]
+	fn rejects_authorities_not_on_the_allow_list() {
+		let authorities = vec![pair(1).public(), pair(2).public()];
+		let allowed = vec![pair(1).public()];
+		assert_eq!(check(&sealed(4, &pair(1)), &authorities, &allowed), Ok(()));
+		assert_eq!(
+			check(&sealed(5, &pair(2)), &authorities, &allowed),
+			Err(Rejection::AuthorNotAllowed)
+		);
+	}
+
+	#[test]
+	fn rejects_without_authorities() {
+		assert_eq!(check(&sealed(4, &pair(1)), &[], &[]), Err(Rejection::UnknownAuthorities));
+	}
+
+	#[test]
+	fn only_forged_headers_disconnect() {
+		assert!(Rejection::Unsealed.disconnect());
+		assert!(Rejection::BadSeal.disconnect());
+		assert!(!Rejection::UnknownParent.disconnect());
+		assert!(!Rejection::UnknownAuthorities.disconnect());
+		assert!(!Rejection::AuthorNotAllowed.disconnect());
+	}
+}
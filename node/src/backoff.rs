@@ -0,0 +1,99 @@
+//! Backing off block authoring while finality lags behind, with Prometheus metrics.
+
+use crate::cli::BackoffParams;
+use node_template_runtime::BlockNumber;
+use sc_consensus_slots::{BackoffAuthoringBlocksStrategy, BackoffAuthoringOnFinalizedHeadLagging};
+use sp_consensus_slots::Slot;
+use substrate_prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+
+/// Authoring backoff metrics.
+struct Metrics {
+	slots_skipped: Counter<U64>,
+	unfinalized_blocks: Gauge<U64>,
+	backing_off: Gauge<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			slots_skipped: register(
+				Counter::new(
+					"authoring_backoff_slots_skipped_total",
+					"Number of slots in which authoring was skipped because finality lags behind",
+				)?,
+				registry,
+			)?,
+			unfinalized_blocks: register(
+				Gauge::new(
+					"authoring_backoff_unfinalized_blocks",
+					"Number of blocks between the best and the finalized block when last authoring",
+				)?,
+				registry,
+			)?,
+			backing_off: register(
+				Gauge::new(
+					"authoring_backoff_active",
+					"Whether authoring was skipped in the last claimed slot (0 or 1)",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// [`BackoffAuthoringOnFinalizedHeadLagging`] that reports its decisions as metrics.
+pub struct AuthoringBackoff {
+	inner: BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>,
+	metrics: Option<Metrics>,
+}
+
+impl AuthoringBackoff {
+	/// Build the strategy from the CLI parameters, or `None` if backoff is disabled.
+	pub fn new(
+		params: &BackoffParams,
+		registry: Option<&Registry>,
+	) -> Result<Option<Self>, PrometheusError> {
+		if params.no_authoring_backoff {
+			return Ok(None)
+		}
+
+		let inner = BackoffAuthoringOnFinalizedHeadLagging {
+			max_interval: params.backoff_max_interval,
+			unfinalized_slack: params.backoff_unfinalized_slack,
+			authoring_bias: params.backoff_authoring_bias,
+		};
+		let metrics = registry.map(Metrics::register).transpose()?;
+
+		Ok(Some(Self { inner, metrics }))
+	}
+}
+
+impl BackoffAuthoringBlocksStrategy<BlockNumber> for AuthoringBackoff {
+	fn should_backoff(
+		&self,
+		chain_head_number: BlockNumber,
+		chain_head_slot: Slot,
+		finalized_number: BlockNumber,
+		slot_now: Slot,
+		logging_target: &str,
+	) -> bool {
+		let backoff = self.inner.should_backoff(
+			chain_head_number,
+			chain_head_slot,
+			finalized_number,
+			slot_now,
+			logging_target,
+		);
+
+		if let Some(metrics) = &self.metrics {
+			let unfinalized = chain_head_number.saturating_sub(finalized_number);
+			metrics.unfinalized_blocks.set(unfinalized.into());
+			metrics.backing_off.set(backoff.into());
+			if backoff {
+				metrics.slots_skipped.inc();
+			}
+		}
+
+		backoff
+	}
+}
@@ -1,18 +1,56 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BabeConfig, Balance, BalancesConfig, Faucet, FaucetConfig,
+	GenesisConfig, GrandpaConfig, IdentityRegistrarsConfig, NodeAuthorizationConfig, OracleConfig,
+	Signature, SudoConfig, SystemConfig, TemplateWritersConfig, BABE_GENESIS_EPOCH_CONFIG,
+	WASM_BINARY,
 };
+use sc_chain_spec::ChainSpecExtension;
 use sc_service::ChainType;
+use serde::{Deserialize, Serialize};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_core::{sr25519, OpaquePeerId, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// Node `ChainSpec` extensions.
+///
+/// Additional parameters for some Substrate core modules, customizable from the chain spec.
+#[derive(Default, Clone, Serialize, Deserialize, ChainSpecExtension)]
+#[serde(rename_all = "camelCase")]
+pub struct Extensions {
+	/// A checkpoint of the finalized chain for light clients to start syncing from. Filled in by
+	/// the `export-sync-spec` subcommand of BABE nodes; always empty on Aura chains.
+	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// The only Aura authorities whose block announcements are accepted, unless overridden with
+	/// `--announce-author`. Empty to accept any current authority.
+	#[serde(default)]
+	pub announce_authors: AnnounceAuthors,
+	/// Transaction pool limits for nodes of this chain, unless overridden with `--pool-limit` or
+	/// `--pool-kbytes`. The Substrate defaults apply if unset.
+	#[serde(default)]
+	pub pool_limits: Option<PoolLimits>,
+}
+
+/// Aura authorities whose block announcements are accepted.
+pub type AnnounceAuthors = Vec<AuraId>;
+
+/// Limits of the ready queue of the transaction pool. As with the command line options, the
+/// future queue gets a tenth of each.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PoolLimits {
+	/// Maximum number of transactions.
+	pub ready_count: usize,
+	/// Maximum total size of the transactions, in kilobytes.
+	pub ready_kbytes: usize,
+}
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, Extensions>;
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
@@ -31,9 +69,20 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate the block authoring (Aura and BABE) and finality keys of an authority.
+pub fn authority_keys_from_seed(s: &str) -> (AuraId, BabeId, GrandpaId) {
+	(get_from_seed::<AuraId>(s), get_from_seed::<BabeId>(s), get_from_seed::<GrandpaId>(s))
+}
+
+// Peer ids of the nodes started with `--node-key` 0x00..01, 0x00..02 and 0x00..03 respectively.
+const ALICE_PEER_ID: &str = "12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+const BOB_PEER_ID: &str = "12D3KooWHdiAxVd8uMQR1hGWXccidmfCwLqcMpGwR6QcTP6QRMuD";
+const CHARLIE_PEER_ID: &str = "12D3KooWSCufgHzV4fCwRijfH2k3abrpAJxTKxEvN1FDuRXA2U9x";
+
+/// A well-known node of the permissioned network, owned by the account of `owner_seed`.
+pub fn well_known_node(peer_id: &str, owner_seed: &str) -> (OpaquePeerId, AccountId) {
+	let peer_id = bs58::decode(peer_id).into_vec().expect("static values are valid; qed");
+	(OpaquePeerId(peer_id), get_account_id_from_seed::<sr25519::Public>(owner_seed))
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -59,6 +108,10 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Well-known nodes
+				vec![well_known_node(ALICE_PEER_ID, "Alice")],
+				true,
+				// Faucet, which live chains leave disabled
 				true,
 			)
 		},
@@ -71,7 +124,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Properties
 		None,
 		// Extensions
-		None,
+		Default::default(),
 	))
 }
 
@@ -106,6 +159,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Well-known nodes
+				vec![
+					well_known_node(ALICE_PEER_ID, "Alice"),
+					well_known_node(BOB_PEER_ID, "Bob"),
+					well_known_node(CHARLIE_PEER_ID, "Charlie"),
+				],
+				true,
+				// Faucet, which live chains leave disabled
 				true,
 			)
 		},
@@ -117,19 +178,31 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		None,
 		// Properties
 		None,
-		// Extensions
-		None,
+		// Extensions; several local nodes usually share one machine.
+		Extensions {
+			pool_limits: Some(PoolLimits { ready_count: 2048, ready_kbytes: 5120 }),
+			..Default::default()
+		},
 	))
 }
 
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AuraId, BabeId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	well_known_nodes: Vec<(OpaquePeerId, AccountId)>,
 	_enable_println: bool,
+	enable_faucet: bool,
 ) -> GenesisConfig {
+	let mut balances: Vec<(AccountId, Balance)> =
+		endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect();
+	if enable_faucet {
+		// Seed the faucet so that fresh accounts can be topped up without going through Alice.
+		balances.push((Faucet::account_id(), 1 << 60));
+	}
+
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
@@ -137,18 +210,34 @@ fn testnet_genesis(
 		},
 		balances: BalancesConfig {
 			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			balances,
 		},
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
 		},
-		grandpa: GrandpaConfig {
+		babe: BabeConfig {
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+			epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
+		},
+		grandpa: GrandpaConfig {
+			authorities: initial_authorities.iter().map(|x| (x.2.clone(), 1)).collect(),
 		},
 		sudo: SudoConfig {
 			// Assign network admin rights.
-			key: root_key,
+			key: root_key.clone(),
 		},
+		node_authorization: NodeAuthorizationConfig { nodes: well_known_nodes },
 		transaction_payment: Default::default(),
+		assets: Default::default(),
+		faucet: FaucetConfig { enabled: enable_faucet },
+		// Let the endowed accounts feed the oracle; members are managed through sudo afterwards.
+		oracle: OracleConfig { members: endowed_accounts },
+		// The sudo key's template writes are prioritized; writers are managed through sudo.
+		template_writers: TemplateWritersConfig {
+			members: vec![root_key.clone()],
+			phantom: Default::default(),
+		},
+		// The sudo key judges identities until more registrars are added.
+		identity_registrars: IdentityRegistrarsConfig { registrars: vec![root_key] },
 	}
 }
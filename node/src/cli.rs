@@ -1,4 +1,6 @@
 use sc_cli::RunCmd;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_core::crypto::Ss58Codec;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +10,57 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	#[structopt(flatten)]
+	pub backoff: BackoffParams,
+
+	/// Follow finality with the GRANDPA observer instead of running the full voter.
+	///
+	/// The observer only processes commit messages and so uses fewer resources, which suits RPC
+	/// and archive nodes. It cannot be used by authorities.
+	#[structopt(long)]
+	pub grandpa_observer: bool,
+
+	/// Only accept block announcements sealed by this Aura authority (SS58 public key). May be
+	/// given several times; overrides the allow-list of the chain spec. Not supported with BABE.
+	#[structopt(
+		long = "announce-author",
+		value_name = "PUBLIC_KEY",
+		parse(try_from_str = parse_aura_id)
+	)]
+	pub announce_authors: Vec<AuraId>,
+
+	/// Not supported: this node has no light client mode. Follow the chain with smoldot instead.
+	#[structopt(long, hidden = true)]
+	pub light: bool,
+}
+
+fn parse_aura_id(key: &str) -> Result<AuraId, String> {
+	AuraId::from_ss58check(key).map_err(|e| format!("Invalid Aura public key: {:?}", e))
+}
+
+/// Parameters of the strategy that slows down block authoring while finality lags behind.
+///
+/// Once more than `--backoff-unfinalized-slack` blocks are unfinalized, the node only authors in
+/// every n-th slot, where n grows with the number of unfinalized blocks divided by
+/// `--backoff-authoring-bias` and is capped at `--backoff-max-interval`.
+#[derive(Debug, Clone, StructOpt)]
+pub struct BackoffParams {
+	/// Author in every claimed slot, no matter how far finality lags behind.
+	#[structopt(long)]
+	pub no_authoring_backoff: bool,
+
+	/// The number of unfinalized blocks tolerated before authoring starts backing off.
+	#[structopt(long, value_name = "BLOCKS", default_value = "50")]
+	pub backoff_unfinalized_slack: u32,
+
+	/// How many unfinalized blocks add one slot to the authoring interval.
+	#[structopt(long, value_name = "BLOCKS", default_value = "2")]
+	pub backoff_authoring_bias: u32,
+
+	/// The maximum number of slots between two authored blocks while backing off.
+	#[structopt(long, value_name = "SLOTS", default_value = "100")]
+	pub backoff_max_interval: u32,
 }
 
 #[derive(Debug, StructOpt)]
@@ -26,9 +79,17 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a chain spec embedding the latest finalized block, GRANDPA authority set and BABE
+	/// epoch changes, so light clients can start syncing from there. Requires `--features babe`.
+	ExportSyncSpec(crate::sync_spec::ExportSyncSpecCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Write the finalized blocks, extrinsics and events to a SQLite database, resuming after
+	/// the last block already written.
+	Index(crate::indexer::IndexCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
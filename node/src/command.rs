@@ -64,6 +64,19 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::Index(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				// The events and metadata of old blocks are read from their state.
+				if !config.state_pruning.is_archive() {
+					return Err(sc_cli::Error::Input(
+						"Indexing needs the state of all blocks; use `--pruning archive`".into(),
+					))
+				}
+				let PartialComponents { client, task_manager, .. } = service::new_partial(&config)?;
+				Ok((cmd.run(client), task_manager))
+			})
+		},
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -78,6 +91,10 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
+		Some(Subcommand::ExportSyncSpec(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(config))
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -109,9 +126,19 @@ pub fn run() -> sc_cli::Result<()> {
 					.into())
 			},
 		None => {
+			if cli.light {
+				return Err("Light client mode is not supported by this node. Follow the chain with \
+				            smoldot and the raw chain spec instead; BABE nodes can also export a \
+				            checkpoint for it with `export-sync-spec`."
+					.into())
+			}
 			let runner = cli.create_runner(&cli.run)?;
+			let backoff = cli.backoff.clone();
+			let grandpa_observer = cli.grandpa_observer;
+			let announce_authors = cli.announce_authors.clone();
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				service::new_full(config, &backoff, grandpa_observer, announce_authors)
+					.map_err(sc_cli::Error::Service)
 			})
 		},
 	}
@@ -0,0 +1,571 @@
+//! The `index` command, which exports finalized blocks with their extrinsics and events to a
+//! SQLite database for analysis.
+//!
+//! Extrinsics and events are decoded with the metadata of the runtime each block was built with,
+//! so blocks from before a runtime upgrade keep their names and descriptions. Reading the metadata
+//! and events of old blocks needs their state, so the node's database must be an archive
+//! (`--pruning archive`). Only finalized blocks are indexed, so the database never has to be rolled
+//! back, and each run resumes after the highest block already in the database.
+
+use frame_metadata::{
+	PalletMetadata, RuntimeMetadata, RuntimeMetadataPrefixed, RuntimeMetadataV14,
+};
+use node_template_runtime::{opaque::Block, Address, Hash, Signature};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use sc_cli::{CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
+use scale_info::{
+	form::{Form, PortableForm},
+	Field, PortableRegistry, TypeDef, TypeDefPrimitive, Variant,
+};
+use sp_api::{Metadata as _, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::{well_known_keys, StorageKey};
+use sp_runtime::{
+	codec::{Compact, Decode, Encode, Error},
+	generic::BlockId,
+	traits::Header as _,
+	MultiAddress,
+};
+use std::{path::PathBuf, sync::Arc};
+use structopt::StructOpt;
+
+/// The number of blocks written per database transaction.
+const BATCH_SIZE: u32 = 1_000;
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS blocks (
+		number INTEGER PRIMARY KEY,
+		hash TEXT NOT NULL,
+		parent_hash TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS extrinsics (
+		block_number INTEGER NOT NULL,
+		idx INTEGER NOT NULL,
+		signer TEXT,
+		pallet TEXT,
+		call TEXT,
+		data BLOB NOT NULL,
+		PRIMARY KEY (block_number, idx)
+	);
+	CREATE TABLE IF NOT EXISTS events (
+		block_number INTEGER NOT NULL,
+		idx INTEGER NOT NULL,
+		extrinsic_idx INTEGER,
+		pallet TEXT,
+		event TEXT,
+		data BLOB NOT NULL,
+		description TEXT NOT NULL,
+		PRIMARY KEY (block_number, idx)
+	);
+";
+
+type TypeRef = <PortableForm as Form>::Type;
+
+/// The metadata of the runtime code with hash `code_hash`.
+struct CachedMetadata {
+	code_hash: Hash,
+	metadata: RuntimeMetadataV14,
+}
+
+/// A decoded event of a block.
+struct EventRow<'a> {
+	extrinsic_idx: Option<u32>,
+	pallet: &'a str,
+	event: &'a str,
+	data: Vec<u8>,
+	description: String,
+}
+
+/// The `index` command.
+#[derive(Debug, StructOpt)]
+pub struct IndexCmd {
+	/// The SQLite file to write to. Created if it does not exist.
+	#[structopt(parse(from_os_str))]
+	pub output: PathBuf,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl IndexCmd {
+	/// Index the finalized blocks of `client` that are not in the database yet.
+	pub async fn run<B, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		B: Backend<Block>,
+		C: HeaderBackend<Block>
+			+ BlockBackend<Block>
+			+ StorageProvider<Block, B>
+			+ ProvideRuntimeApi<Block>,
+		C::Api: sp_api::Metadata<Block>,
+	{
+		let mut db = Connection::open(&self.output).map_err(db_error)?;
+		db.execute_batch(SCHEMA).map_err(db_error)?;
+
+		let last: Option<u32> = db
+			.query_row("SELECT MAX(number) FROM blocks", [], |row| row.get(0))
+			.optional()
+			.map_err(db_error)?
+			.flatten();
+		let from = last.map_or(0, |n| n + 1);
+		let to = client.info().finalized_number;
+
+		let mut metadata = None;
+		let mut start = from;
+		while start <= to {
+			let end = to.min(start.saturating_add(BATCH_SIZE - 1));
+			let tx = db.transaction().map_err(db_error)?;
+			for number in start..=end {
+				index_block(&tx, &*client, &mut metadata, number)?;
+			}
+			tx.commit().map_err(db_error)?;
+			start = end + 1;
+		}
+
+		if from <= to {
+			log::info!("Indexed blocks #{}..=#{} into {}", from, to, self.output.display());
+		} else {
+			log::info!("Index is up to date at block #{}", to);
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for IndexCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+fn db_error(e: rusqlite::Error) -> sc_cli::Error {
+	sc_cli::Error::Application(Box::new(e))
+}
+
+/// Write block `number` with its extrinsics and events.
+fn index_block<B, C>(
+	tx: &Transaction,
+	client: &C,
+	metadata: &mut Option<CachedMetadata>,
+	number: u32,
+) -> sc_cli::Result<()>
+where
+	B: Backend<Block>,
+	C: HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ StorageProvider<Block, B>
+		+ ProvideRuntimeApi<Block>,
+	C::Api: sp_api::Metadata<Block>,
+{
+	let missing =
+		|| sc_cli::Error::Input(format!("Block #{} is missing from the database", number));
+	let hash = client.hash(number)?.ok_or_else(missing)?;
+	let id = BlockId::Hash(hash);
+	let header = client.header(id)?.ok_or_else(missing)?;
+	// A block is executed by the runtime code in the state of its parent, even if it upgrades it.
+	let parent = if number == 0 { id } else { BlockId::Hash(*header.parent_hash()) };
+	let metadata = metadata_at(client, metadata, &parent, number)?;
+
+	for (idx, opaque) in client.block_body(&id)?.unwrap_or_default().iter().enumerate() {
+		let data = opaque.encode();
+		let (signer, pallet, call) = match decode_extrinsic(metadata, &data) {
+			Ok((signer, pallet, call)) => (signer, Some(pallet), Some(call)),
+			Err(_) => (None, None, None),
+		};
+		tx.execute(
+			"INSERT INTO extrinsics (block_number, idx, signer, pallet, call, data)
+			VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+			params![number, idx as u32, signer, pallet, call, data],
+		)
+		.map_err(db_error)?;
+	}
+
+	let events = match client.storage(&id, &crate::template_metrics::events_key())? {
+		Some(data) => decode_events(metadata, &data.0)
+			.map_err(|e| sc_cli::Error::Input(format!("Invalid events in #{}: {}", number, e)))?,
+		None => Vec::new(),
+	};
+	for (idx, event) in events.into_iter().enumerate() {
+		tx.execute(
+			"INSERT INTO events (block_number, idx, extrinsic_idx, pallet, event, data, description)
+			VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+			params![
+				number,
+				idx as u32,
+				event.extrinsic_idx,
+				event.pallet,
+				event.event,
+				event.data,
+				event.description
+			],
+		)
+		.map_err(db_error)?;
+	}
+
+	tx.execute(
+		"INSERT INTO blocks (number, hash, parent_hash) VALUES (?1, ?2, ?3)",
+		params![number, format!("{:?}", hash), format!("{:?}", header.parent_hash())],
+	)
+	.map_err(db_error)?;
+	Ok(())
+}
+
+/// The metadata of the runtime at `id`, read again only when the runtime code changed.
+fn metadata_at<'a, B, C>(
+	client: &C,
+	cache: &'a mut Option<CachedMetadata>,
+	id: &BlockId<Block>,
+	number: u32,
+) -> sc_cli::Result<&'a RuntimeMetadataV14>
+where
+	B: Backend<Block>,
+	C: StorageProvider<Block, B> + ProvideRuntimeApi<Block>,
+	C::Api: sp_api::Metadata<Block>,
+{
+	let no_state = || {
+		sc_cli::Error::Input(format!(
+			"The state of block #{} is pruned; index an archive node (`--pruning archive`)",
+			number
+		))
+	};
+	let code_hash = client
+		.storage_hash(id, &StorageKey(well_known_keys::CODE.to_vec()))
+		.map_err(|_| no_state())?
+		.ok_or_else(no_state)?;
+
+	if cache.as_ref().map_or(true, |cached| cached.code_hash != code_hash) {
+		let opaque = client.runtime_api().metadata(id).map_err(|_| no_state())?;
+		let metadata = match RuntimeMetadataPrefixed::decode(&mut &opaque[..]) {
+			Ok(RuntimeMetadataPrefixed(_, RuntimeMetadata::V14(metadata))) => metadata,
+			_ =>
+				return Err(sc_cli::Error::Input(format!(
+					"The runtime of block #{} has unsupported metadata",
+					number
+				))),
+		};
+		*cache = Some(CachedMetadata { code_hash, metadata });
+	}
+	Ok(&cache.as_ref().expect("filled in above; qed").metadata)
+}
+
+/// The signer, pallet and call names of an encoded extrinsic.
+fn decode_extrinsic<'a>(
+	metadata: &'a RuntimeMetadataV14,
+	encoded: &[u8],
+) -> Result<(Option<String>, &'a str, &'a str), Error> {
+	let input = &mut &encoded[..];
+	Compact::<u32>::decode(input)?;
+	let version = u8::decode(input)?;
+	if version & 0b0111_1111 != metadata.extrinsic.version {
+		return Err("Unsupported extrinsic version".into())
+	}
+
+	let signer = if version & 0b1000_0000 != 0 {
+		let address = Address::decode(input)?;
+		Signature::decode(input)?;
+		for extension in &metadata.extrinsic.signed_extensions {
+			describe(&metadata.types, &extension.ty, input, &mut String::new())?;
+		}
+		Some(match address {
+			MultiAddress::Id(account) => account.to_string(),
+			other => format!("{:?}", other),
+		})
+	} else {
+		None
+	};
+
+	let (pallet, call) =
+		pallet_variant(metadata, input, |pallet| pallet.calls.as_ref().map(|c| &c.ty))?;
+	Ok((signer, pallet, call.name().as_str()))
+}
+
+/// The events of a block, encoded as `Vec<frame_system::EventRecord>`.
+fn decode_events<'a>(
+	metadata: &'a RuntimeMetadataV14,
+	encoded: &[u8],
+) -> Result<Vec<EventRow<'a>>, Error> {
+	let input = &mut &encoded[..];
+	let count = Compact::<u32>::decode(input)?.0;
+	(0..count)
+		.map(|_| {
+			let extrinsic_idx = match frame_system::Phase::decode(input)? {
+				frame_system::Phase::ApplyExtrinsic(i) => Some(i),
+				_ => None,
+			};
+
+			let start = *input;
+			let (pallet, event) =
+				pallet_variant(metadata, input, |pallet| pallet.event.as_ref().map(|e| &e.ty))?;
+			let mut description = format!("{}({}", pallet, event.name().as_str());
+			describe_fields(&metadata.types, event.fields(), input, &mut description)?;
+			description.push(')');
+			let data = start[..start.len() - input.len()].to_vec();
+
+			Vec::<Hash>::decode(input)?;
+			Ok(EventRow { extrinsic_idx, pallet, event: event.name().as_str(), data, description })
+		})
+		.collect()
+}
+
+/// The pallet and variant of an encoded outer runtime `Call` or `Event`, whose first byte selects
+/// the pallet and second byte the variant of the pallet's type returned by `ty`.
+fn pallet_variant<'a>(
+	metadata: &'a RuntimeMetadataV14,
+	input: &mut &[u8],
+	ty: fn(&PalletMetadata<PortableForm>) -> Option<&TypeRef>,
+) -> Result<(&'a str, &'a Variant<PortableForm>), Error> {
+	let index = u8::decode(input)?;
+	let pallet = metadata
+		.pallets
+		.iter()
+		.find(|pallet| pallet.index == index)
+		.ok_or("Unknown pallet")?;
+	let variants = match ty(pallet)
+		.and_then(|ty| metadata.types.resolve(ty.id()))
+		.map(|ty| ty.type_def())
+	{
+		Some(TypeDef::Variant(def)) => def.variants(),
+		_ => return Err("The pallet has no such calls or events".into()),
+	};
+	let index = u8::decode(input)?;
+	let variant = variants.iter().find(|v| v.index() == index).ok_or("Unknown variant")?;
+	Ok((pallet.name.as_str(), variant))
+}
+
+/// Decode a value of type `ty` from `input`, appending a description of it to `out`.
+fn describe(
+	types: &PortableRegistry,
+	ty: &TypeRef,
+	input: &mut &[u8],
+	out: &mut String,
+) -> Result<(), Error> {
+	match types.resolve(ty.id()).ok_or("Unknown type")?.type_def() {
+		TypeDef::Composite(def) => describe_fields(types, def.fields(), input, out),
+		TypeDef::Variant(def) => {
+			let index = u8::decode(input)?;
+			let variant =
+				def.variants().iter().find(|v| v.index() == index).ok_or("Unknown variant")?;
+			out.push_str(variant.name().as_str());
+			describe_fields(types, variant.fields(), input, out)
+		},
+		TypeDef::Sequence(def) => {
+			let len = Compact::<u32>::decode(input)?.0;
+			describe_items(types, def.type_param(), len, input, out)
+		},
+		TypeDef::Array(def) => describe_items(types, def.type_param(), def.len(), input, out),
+		TypeDef::Tuple(def) => {
+			out.push('(');
+			for (i, field) in def.fields().iter().enumerate() {
+				if i > 0 {
+					out.push_str(", ");
+				}
+				describe(types, field, input, out)?;
+			}
+			out.push(')');
+			Ok(())
+		},
+		TypeDef::Primitive(def) => {
+			let value = match def {
+				TypeDefPrimitive::Bool => bool::decode(input)?.to_string(),
+				TypeDefPrimitive::Char =>
+					char::from_u32(u32::decode(input)?).ok_or("Invalid char")?.to_string(),
+				TypeDefPrimitive::Str => format!("{:?}", String::decode(input)?),
+				TypeDefPrimitive::U8 => u8::decode(input)?.to_string(),
+				TypeDefPrimitive::U16 => u16::decode(input)?.to_string(),
+				TypeDefPrimitive::U32 => u32::decode(input)?.to_string(),
+				TypeDefPrimitive::U64 => u64::decode(input)?.to_string(),
+				TypeDefPrimitive::U128 => u128::decode(input)?.to_string(),
+				TypeDefPrimitive::I8 => i8::decode(input)?.to_string(),
+				TypeDefPrimitive::I16 => i16::decode(input)?.to_string(),
+				TypeDefPrimitive::I32 => i32::decode(input)?.to_string(),
+				TypeDefPrimitive::I64 => i64::decode(input)?.to_string(),
+				TypeDefPrimitive::I128 => i128::decode(input)?.to_string(),
+				TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => hex(&<[u8; 32]>::decode(input)?),
+			};
+			out.push_str(&value);
+			Ok(())
+		},
+		TypeDef::Compact(_) => {
+			out.push_str(&Compact::<u128>::decode(input)?.0.to_string());
+			Ok(())
+		},
+		// Bit sequences only exist with the `bit-vec` feature of `scale-info`.
+		#[allow(unreachable_patterns)]
+		_ => Err("Unsupported type".into()),
+	}
+}
+
+/// Describe the fields of a struct or enum variant, like their `Debug` output.
+fn describe_fields(
+	types: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	input: &mut &[u8],
+	out: &mut String,
+) -> Result<(), Error> {
+	if fields.is_empty() {
+		return Ok(())
+	}
+	let named = fields[0].name().is_some();
+	out.push_str(if named { " { " } else { "(" });
+	for (i, field) in fields.iter().enumerate() {
+		if i > 0 {
+			out.push_str(", ");
+		}
+		if let Some(name) = field.name() {
+			out.push_str(name);
+			out.push_str(": ");
+		}
+		describe(types, field.ty(), input, out)?;
+	}
+	out.push_str(if named { " }" } else { ")" });
+	Ok(())
+}
+
+/// Describe `len` items of type `ty`; bytes are shown in hex.
+fn describe_items(
+	types: &PortableRegistry,
+	ty: &TypeRef,
+	len: u32,
+	input: &mut &[u8],
+	out: &mut String,
+) -> Result<(), Error> {
+	let len = len as usize;
+	let is_byte = matches!(
+		types.resolve(ty.id()).map(|ty| ty.type_def()),
+		Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+	);
+	if is_byte {
+		if input.len() < len {
+			return Err("Not enough data".into())
+		}
+		let (bytes, rest) = input.split_at(len);
+		out.push_str(&hex(bytes));
+		*input = rest;
+		return Ok(())
+	}
+
+	out.push('[');
+	for i in 0..len {
+		if i > 0 {
+			out.push_str(", ");
+		}
+		describe(types, ty, input, out)?;
+	}
+	out.push(']');
+	Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+	let digits: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+	format!("0x{}", digits)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use node_template_runtime::{Call, Event, Runtime, UncheckedExtrinsic};
+	use sp_core::crypto::AccountId32;
+
+	fn metadata() -> RuntimeMetadataV14 {
+		match Runtime::metadata().1 {
+			RuntimeMetadata::V14(metadata) => metadata,
+			_ => unreachable!("the runtime uses metadata V14; qed"),
+		}
+	}
+
+	fn record(phase: frame_system::Phase, event: Event) -> frame_system::EventRecord<Event, Hash> {
+		frame_system::EventRecord { phase, event, topics: vec![Hash::repeat_byte(1)] }
+	}
+
+	fn remark() -> Vec<u8> {
+		let call = Call::System(frame_system::Call::remark { remark: vec![1, 2] });
+		UncheckedExtrinsic::new_unsigned(call).encode()
+	}
+
+	#[test]
+	fn decodes_unsigned_extrinsics() {
+		assert_eq!(decode_extrinsic(&metadata(), &remark()).unwrap(), (None, "System", "remark"));
+	}
+
+	#[test]
+	fn rejects_unknown_extrinsics() {
+		let metadata = metadata();
+
+		// The length is followed by the version, the pallet index and the call index.
+		let mut xt = remark();
+		xt[1] = 3;
+		assert!(decode_extrinsic(&metadata, &xt).is_err());
+
+		let mut xt = remark();
+		xt[2] = u8::MAX;
+		assert!(decode_extrinsic(&metadata, &xt).is_err());
+
+		let mut xt = remark();
+		xt[3] = u8::MAX;
+		assert!(decode_extrinsic(&metadata, &xt).is_err());
+	}
+
+	#[test]
+	fn decodes_events_with_their_phase() {
+		let metadata = metadata();
+		let who = AccountId32::new([2; 32]);
+		let created = Event::System(frame_system::Event::NewAccount(who.clone()));
+		let killed = Event::System(frame_system::Event::KilledAccount(who));
+		let events = vec![
+			record(frame_system::Phase::ApplyExtrinsic(1), created.clone()),
+			record(frame_system::Phase::Finalization, killed.clone()),
+		]
+		.encode();
+
+		let rows = decode_events(&metadata, &events).unwrap();
+		assert_eq!(rows.len(), 2);
+		assert_eq!(rows[0].extrinsic_idx, Some(1));
+		assert_eq!((rows[0].pallet, rows[0].event), ("System", "NewAccount"));
+		assert_eq!(rows[0].data, created.encode());
+		assert_eq!(rows[1].extrinsic_idx, None);
+		assert_eq!((rows[1].pallet, rows[1].event), ("System", "KilledAccount"));
+		assert_eq!(rows[1].data, killed.encode());
+	}
+
+	#[test]
+	fn describes_event_fields() {
+		let metadata = metadata();
+		let who = AccountId32::new([2; 32]);
+		let events = vec![record(
+			frame_system::Phase::Initialization,
+			Event::System(frame_system::Event::Remarked(who, Hash::repeat_byte(3))),
+		)]
+		.encode();
+
+		let rows = decode_events(&metadata, &events).unwrap();
+		assert_eq!(
+			rows[0].description,
+			format!("System(Remarked(({}), ({})))", hex(&[2; 32]), hex(&[3; 32]))
+		);
+	}
+
+	#[test]
+	fn rejects_truncated_events() {
+		let metadata = metadata();
+		let event = Event::System(frame_system::Event::NewAccount(AccountId32::new([2; 32])));
+		let events = vec![record(frame_system::Phase::Initialization, event)].encode();
+
+		assert!(decode_events(&metadata, &events[..events.len() - 1]).is_err());
+	}
+}
@@ -1,3 +1,9 @@
+pub mod announce;
+pub mod backoff;
 pub mod chain_spec;
+pub mod cli;
+pub mod indexer;
 pub mod rpc;
 pub mod service;
+pub mod sync_spec;
+pub mod template_metrics;
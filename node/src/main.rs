@@ -1,12 +1,17 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
+mod announce;
+mod backoff;
 mod chain_spec;
 #[macro_use]
 mod service;
 mod cli;
 mod command;
+mod indexer;
 mod rpc;
+mod sync_spec;
+mod template_metrics;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
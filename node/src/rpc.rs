@@ -7,48 +7,167 @@
 
 use std::sync::Arc;
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use node_template_runtime::{
+	opaque::Block, AccountId, Balance, BlockNumber, ClassId, Hash, Index, InstanceId,
+};
+use sc_finality_grandpa::{
+	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+};
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
+/// Runtime APIs the RPC extensions need on top of the ones every node has: BABE nodes serve the
+/// BABE RPC, which queries the BABE runtime API.
+#[cfg(feature = "babe")]
+pub trait ConsensusRuntimeApi: sp_consensus_babe::BabeApi<Block> {}
+
+#[cfg(feature = "babe")]
+impl<T: sp_consensus_babe::BabeApi<Block>> ConsensusRuntimeApi for T {}
+
+/// Aura nodes need no runtime APIs beyond the ones every node has.
+#[cfg(not(feature = "babe"))]
+pub trait ConsensusRuntimeApi {}
+
+#[cfg(not(feature = "babe"))]
+impl<T> ConsensusRuntimeApi for T {}
+
+/// Extra dependencies for BABE.
+#[cfg(feature = "babe")]
+pub struct BabeDeps<B> {
+	/// The chain specification the node runs with, served by the sync state RPC.
+	pub chain_spec: Box<dyn sc_chain_spec::ChainSpec>,
+	/// The `SelectChain` strategy.
+	pub select_chain: sc_consensus::LongestChain<B, Block>,
+	/// BABE protocol config.
+	pub babe_config: sc_consensus_babe::Config,
+	/// BABE pending epoch changes.
+	pub shared_epoch_changes:
+		sc_consensus_epochs::SharedEpochChanges<Block, sc_consensus_babe::Epoch>,
+	/// The keystore that manages the keys of the node.
+	pub keystore: sp_keystore::SyncCryptoStorePtr,
+}
+
+/// Extra dependencies for GRANDPA.
+pub struct GrandpaDeps<B> {
+	/// Voting round info.
+	pub shared_voter_state: SharedVoterState,
+	/// Authority set info.
+	pub shared_authority_set: SharedAuthoritySet<Hash, BlockNumber>,
+	/// Receives notifications about justification events from GRANDPA.
+	pub justification_stream: GrandpaJustificationStream<Block>,
+	/// Executor to drive the subscription manager in the GRANDPA RPC handler.
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Finality proof provider.
+	pub finality_provider: Arc<FinalityProofProvider<B, Block>>,
+}
+
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// BABE specific dependencies.
+	#[cfg(feature = "babe")]
+	pub babe: BabeDeps<B>,
+	/// GRANDPA specific dependencies.
+	pub grandpa: GrandpaDeps<B>,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+pub fn create_full<C, P, B>(
+	deps: FullDeps<C, P, B>,
+) -> Result<jsonrpc_core::IoHandler<sc_rpc::Metadata>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
-	C: Send + Sync + 'static,
+	C: sc_client_api::AuxStore + Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: pallet_poe_rpc::PoeRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	C::Api: pallet_uniques_rpc::UniquesRuntimeApi<Block, AccountId, ClassId, InstanceId>,
+	C::Api: ConsensusRuntimeApi,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
+	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+	use pallet_contracts_rpc::{Contracts, ContractsApi};
+	use pallet_poe_rpc::{Poe, PoeApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_uniques_rpc::{Uniques, UniquesApi};
+	use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	#[cfg(not(feature = "babe"))]
+	let FullDeps { client, pool, deny_unsafe, grandpa } = deps;
+	#[cfg(feature = "babe")]
+	let FullDeps { client, pool, deny_unsafe, babe, grandpa } = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
+
+	io.extend_with(PoeApi::to_delegate(Poe::new(client.clone())));
+
+	io.extend_with(UniquesApi::to_delegate(Uniques::new(client.clone())));
+
+	let GrandpaDeps {
+		shared_voter_state,
+		shared_authority_set,
+		justification_stream,
+		subscription_executor,
+		finality_provider,
+	} = grandpa;
+
+	#[cfg(feature = "babe")]
+	{
+		use sc_consensus_babe_rpc::{BabeApi, BabeRpcHandler};
+		use sc_sync_state_rpc::{SyncStateRpcApi, SyncStateRpcHandler};
+
+		let BabeDeps { chain_spec, select_chain, babe_config, shared_epoch_changes, keystore } =
+			babe;
+		io.extend_with(BabeApi::to_delegate(BabeRpcHandler::new(
+			client.clone(),
+			shared_epoch_changes.clone(),
+			keystore,
+			babe_config,
+			select_chain,
+			deny_unsafe,
+		)));
+
+		// The light sync state includes the BABE epoch changes, so only BABE nodes can serve it.
+		io.extend_with(SyncStateRpcApi::to_delegate(SyncStateRpcHandler::new(
+			chain_spec,
+			client.clone(),
+			shared_authority_set.clone(),
+			shared_epoch_changes,
+			deny_unsafe,
+		)?));
+	}
+
+	io.extend_with(GrandpaApi::to_delegate(GrandpaRpcHandler::new(
+		shared_authority_set,
+		shared_voter_state,
+		justification_stream,
+		subscription_executor,
+		finality_provider,
+	)));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
 	// `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
 
-	io
+	Ok(io)
 }
@@ -1,14 +1,22 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
+use crate::{
+	backoff::AuthoringBackoff,
+	chain_spec::{AnnounceAuthors, PoolLimits},
+	cli::BackoffParams,
+};
 use node_template_runtime::{self, opaque::Block, RuntimeApi};
 use sc_client_api::ExecutorProvider;
-use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_aura::SlotProportion;
+#[cfg(not(feature = "babe"))]
+use sc_consensus_aura::{ImportQueueParams, StartAuraParams};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus::SlotData;
+#[cfg(not(feature = "babe"))]
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use std::{sync::Arc, time::Duration};
 
@@ -36,6 +44,23 @@ type FullClient =
 	sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type FullGrandpaBlockImport =
+	sc_finality_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
+
+/// The block import used by the authoring engine. Aura imports straight into GRANDPA.
+#[cfg(not(feature = "babe"))]
+type FullBlockImport = FullGrandpaBlockImport;
+/// The block import used by the authoring engine. BABE wraps the GRANDPA block import.
+#[cfg(feature = "babe")]
+type FullBlockImport =
+	sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>;
+
+/// State shared between the import queue and the authoring task. Aura does not need any.
+#[cfg(not(feature = "babe"))]
+type ConsensusLink = ();
+/// State shared between the import queue and the authoring task.
+#[cfg(feature = "babe")]
+type ConsensusLink = sc_consensus_babe::BabeLink<Block>;
 
 pub fn new_partial(
 	config: &Configuration,
@@ -47,13 +72,9 @@ pub fn new_partial(
 		sc_consensus::DefaultImportQueue<Block, FullClient>,
 		sc_transaction_pool::FullPool<Block, FullClient>,
 		(
-			sc_finality_grandpa::GrandpaBlockImport<
-				FullBackend,
-				Block,
-				FullClient,
-				FullSelectChain,
-			>,
+			FullBlockImport,
 			sc_finality_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
+			ConsensusLink,
 			Option<Telemetry>,
 		),
 	>,
@@ -110,32 +131,72 @@ pub fn new_partial(
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
-	let slot_duration = sc_consensus_aura::slot_duration(&*client)?.slot_duration();
+	#[cfg(not(feature = "babe"))]
+	let (block_import, consensus_link, import_queue) = {
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?.slot_duration();
 
-	let import_queue =
-		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
-			block_import: grandpa_block_import.clone(),
-			justification_import: Some(Box::new(grandpa_block_import.clone())),
-			client: client.clone(),
-			create_inherent_data_providers: move |_, ()| async move {
+		let import_queue =
+			sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
+				block_import: grandpa_block_import.clone(),
+				justification_import: Some(Box::new(grandpa_block_import.clone())),
+				client: client.clone(),
+				create_inherent_data_providers: move |_, ()| async move {
+					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+					let slot =
+						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
+							*timestamp,
+							slot_duration,
+						);
+
+					Ok((timestamp, slot))
+				},
+				spawner: &task_manager.spawn_essential_handle(),
+				can_author_with: sp_consensus::CanAuthorWithNativeVersion::new(
+					client.executor().clone(),
+				),
+				registry: config.prometheus_registry(),
+				check_for_equivocation: Default::default(),
+				telemetry: telemetry.as_ref().map(|x| x.handle()),
+			})?;
+
+		(grandpa_block_import, (), import_queue)
+	};
+
+	#[cfg(feature = "babe")]
+	let (block_import, consensus_link, import_queue) = {
+		let (block_import, babe_link) = sc_consensus_babe::block_import(
+			sc_consensus_babe::Config::get_or_compute(&*client)?,
+			grandpa_block_import.clone(),
+			client.clone(),
+		)?;
+
+		let slot_duration = babe_link.config().slot_duration();
+		let import_queue = sc_consensus_babe::import_queue(
+			babe_link.clone(),
+			block_import.clone(),
+			Some(Box::new(grandpa_block_import)),
+			client.clone(),
+			select_chain.clone(),
+			move |_, ()| async move {
 				let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
 
 				let slot =
-					sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
+					sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
 						*timestamp,
 						slot_duration,
 					);
 
 				Ok((timestamp, slot))
 			},
-			spawner: &task_manager.spawn_essential_handle(),
-			can_author_with: sp_consensus::CanAuthorWithNativeVersion::new(
-				client.executor().clone(),
-			),
-			registry: config.prometheus_registry(),
-			check_for_equivocation: Default::default(),
-			telemetry: telemetry.as_ref().map(|x| x.handle()),
-		})?;
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+			sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone()),
+			telemetry.as_ref().map(|x| x.handle()),
+		)?;
+
+		(block_import, babe_link, import_queue)
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -145,7 +206,7 @@ pub fn new_partial(
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (grandpa_block_import, grandpa_link, telemetry),
+		other: (block_import, grandpa_link, consensus_link, telemetry),
 	})
 }
 
@@ -156,8 +217,53 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 	Err("Remote Keystore not supported.")
 }
 
+/// Use the transaction pool limits of the chain spec, unless the command line set others.
+fn apply_pool_limits(config: &mut Configuration) {
+	let limits = sc_chain_spec::get_extension::<Option<PoolLimits>>(config.chain_spec.extensions())
+		.cloned()
+		.flatten();
+	let limits = match limits {
+		Some(limits) => limits,
+		None => return,
+	};
+
+	// The command line defaults match the pool's, so unchanged limits were not set explicitly.
+	let pool = &mut config.transaction_pool;
+	let defaults = sc_transaction_pool::Options::default();
+	if pool.ready.count != defaults.ready.count ||
+		pool.ready.total_bytes != defaults.ready.total_bytes
+	{
+		return
+	}
+
+	pool.ready.count = limits.ready_count;
+	pool.ready.total_bytes = limits.ready_kbytes * 1024;
+	pool.future.count = limits.ready_count / 10;
+	pool.future.total_bytes = limits.ready_kbytes * 1024 / 10;
+}
+
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	mut config: Configuration,
+	backoff: &BackoffParams,
+	grandpa_observer: bool,
+	announce_authors: AnnounceAuthors,
+) -> Result<TaskManager, ServiceError> {
+	if grandpa_observer && config.role.is_authority() {
+		return Err(ServiceError::Other(
+			"The GRANDPA observer cannot be run by an authority.".into(),
+		))
+	}
+
+	apply_pool_limits(&mut config);
+
+	// Only the well-known nodes of the runtime's node authorization may connect. Until the
+	// offchain worker makes the allowed nodes reserved peers, only `--reserved-nodes` are.
+	config.network.default_peers_set.non_reserved_mode =
+		sc_network::config::NonReservedPeerMode::Deny;
+
+	// Aura keeps no state in `consensus_link`.
+	#[cfg_attr(not(feature = "babe"), allow(unused_variables))]
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -166,7 +272,7 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		mut keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (block_import, grandpa_link, mut telemetry),
+		other: (block_import, grandpa_link, consensus_link, mut telemetry),
 	} = new_partial(&config)?;
 
 	if let Some(url) = &config.keystore_remote {
@@ -187,6 +293,39 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		Vec::default(),
 	));
 
+	// Announcements are checked against Aura seals, which BABE blocks do not carry.
+	#[cfg(feature = "babe")]
+	let block_announce_validator_builder = {
+		if !announce_authors.is_empty() {
+			return Err(ServiceError::Other(
+				"`--announce-author` is only supported with Aura.".into(),
+			))
+		}
+		None
+	};
+	#[cfg(not(feature = "babe"))]
+	let block_announce_validator_builder = {
+		use sp_consensus::block_validation::BlockAnnounceValidator;
+
+		// The command line takes precedence over the chain spec.
+		let allowed_authors = if announce_authors.is_empty() {
+			sc_chain_spec::get_extension::<AnnounceAuthors>(config.chain_spec.extensions())
+				.cloned()
+				.unwrap_or_default()
+		} else {
+			announce_authors
+		};
+		let validator = crate::announce::AuraAnnounceValidator::new(
+			client.clone(),
+			allowed_authors,
+			config.prometheus_registry(),
+		)
+		.map_err(|e| ServiceError::Other(format!("Failed to register announce metrics: {}", e)))?;
+
+		Some(Box::new(move |_| Box::new(validator) as Box<dyn BlockAnnounceValidator<_> + Send>)
+			as Box<_>)
+	};
+
 	let (network, system_rpc_tx, network_starter) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
 			config: &config,
@@ -194,10 +333,13 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 			transaction_pool: transaction_pool.clone(),
 			spawn_handle: task_manager.spawn_handle(),
 			import_queue,
-			block_announce_validator_builder: None,
+			block_announce_validator_builder,
 			warp_sync: Some(warp_sync),
 		})?;
 
+	// The runtime's node authorization offchain worker makes the on-chain allow-list the reserved
+	// peers of this node. It only runs on authorities unless the node is started with
+	// `--offchain-worker always`.
 	if config.offchain_worker.enabled {
 		sc_service::build_offchain_workers(
 			&config,
@@ -205,24 +347,78 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 			client.clone(),
 			network.clone(),
 		);
+	} else {
+		log::warn!(
+			"Offchain workers are disabled, so this node only connects to its `--reserved-nodes` \
+			 instead of the well-known nodes. Start it with `--offchain-worker always` to follow \
+			 the on-chain allow-list."
+		);
 	}
 
 	let role = config.role.clone();
 	let force_authoring = config.force_authoring;
-	let backoff_authoring_blocks: Option<()> = None;
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
+	let backoff_authoring_blocks = AuthoringBackoff::new(backoff, prometheus_registry.as_ref())
+		.map_err(|e| ServiceError::Other(format!("Failed to register backoff metrics: {}", e)))?;
+
+	let justification_stream = grandpa_link.justification_stream();
+	let shared_authority_set = grandpa_link.shared_authority_set().clone();
+	let shared_voter_state = SharedVoterState::empty();
+	let finality_proof_provider = sc_finality_grandpa::FinalityProofProvider::new_for_service(
+		backend.clone(),
+		Some(shared_authority_set.clone()),
+	);
+
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let metrics = crate::template_metrics::Metrics::register(registry).map_err(|e| {
+			ServiceError::Other(format!("Failed to register template metrics: {}", e))
+		})?;
+		task_manager.spawn_handle().spawn(
+			"template-metrics",
+			None,
+			crate::template_metrics::run::<FullBackend, _>(client.clone(), metrics),
+		);
+	}
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		#[cfg(feature = "babe")]
+		let (chain_spec, select_chain, babe_config, shared_epoch_changes, keystore) = (
+			config.chain_spec.cloned_box(),
+			select_chain.clone(),
+			consensus_link.config().clone(),
+			consensus_link.epoch_changes().clone(),
+			keystore_container.sync_keystore(),
+		);
 
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+		let shared_voter_state = shared_voter_state.clone();
 
-			Ok(crate::rpc::create_full(deps))
+		Box::new(move |deny_unsafe, subscription_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				#[cfg(feature = "babe")]
+				babe: crate::rpc::BabeDeps {
+					chain_spec: chain_spec.cloned_box(),
+					select_chain: select_chain.clone(),
+					babe_config: babe_config.clone(),
+					shared_epoch_changes: shared_epoch_changes.clone(),
+					keystore: keystore.clone(),
+				},
+				grandpa: crate::rpc::GrandpaDeps {
+					shared_voter_state: shared_voter_state.clone(),
+					shared_authority_set: shared_authority_set.clone(),
+					justification_stream: justification_stream.clone(),
+					subscription_executor,
+					finality_provider: finality_proof_provider.clone(),
+				},
+			};
+
+			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
 
@@ -251,44 +447,90 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		let can_author_with =
 			sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
 
-		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
-		let raw_slot_duration = slot_duration.slot_duration();
-
-		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _, _>(
-			StartAuraParams {
-				slot_duration,
+		#[cfg(not(feature = "babe"))]
+		{
+			let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+			let raw_slot_duration = slot_duration.slot_duration();
+
+			let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _, _>(
+				StartAuraParams {
+					slot_duration,
+					client: client.clone(),
+					select_chain,
+					block_import,
+					proposer_factory,
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+						let slot =
+							sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
+								*timestamp,
+								raw_slot_duration,
+							);
+
+						Ok((timestamp, slot))
+					},
+					force_authoring,
+					backoff_authoring_blocks,
+					keystore: keystore_container.sync_keystore(),
+					can_author_with,
+					sync_oracle: network.clone(),
+					justification_sync_link: network.clone(),
+					block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+					max_block_proposal_slot_portion: None,
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
+				},
+			)?;
+
+			// the AURA authoring task is considered essential, i.e. if it
+			// fails we take down the service with it.
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"aura",
+				Some("block-authoring"),
+				aura,
+			);
+		}
+
+		#[cfg(feature = "babe")]
+		{
+			let slot_duration = consensus_link.config().slot_duration();
+
+			let babe = sc_consensus_babe::start_babe(sc_consensus_babe::BabeParams {
+				keystore: keystore_container.sync_keystore(),
 				client: client.clone(),
 				select_chain,
+				env: proposer_factory,
 				block_import,
-				proposer_factory,
+				sync_oracle: network.clone(),
+				justification_sync_link: network.clone(),
 				create_inherent_data_providers: move |_, ()| async move {
 					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
 
 					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
+						sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
 							*timestamp,
-							raw_slot_duration,
+							slot_duration,
 						);
 
 					Ok((timestamp, slot))
 				},
 				force_authoring,
 				backoff_authoring_blocks,
-				keystore: keystore_container.sync_keystore(),
+				babe_link: consensus_link,
 				can_author_with,
-				sync_oracle: network.clone(),
-				justification_sync_link: network.clone(),
 				block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
 				max_block_proposal_slot_portion: None,
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
-			},
-		)?;
-
-		// the AURA authoring task is considered essential, i.e. if it
-		// fails we take down the service with it.
-		task_manager
-			.spawn_essential_handle()
-			.spawn_blocking("aura", Some("block-authoring"), aura);
+			})?;
+
+			// the BABE authoring task is considered essential, i.e. if it
+			// fails we take down the service with it.
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"babe-proposer",
+				Some("block-authoring"),
+				babe,
+			);
+		}
 	}
 
 	// if the node isn't actively participating in consensus then it doesn't
@@ -301,26 +543,34 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		gossip_duration: Duration::from_millis(333),
 		justification_period: 512,
 		name: Some(name),
-		observer_enabled: false,
+		observer_enabled: grandpa_observer,
 		keystore,
 		local_role: role,
 		telemetry: telemetry.as_ref().map(|x| x.handle()),
 	};
 
-	if enable_grandpa {
+	if enable_grandpa && grandpa_observer {
+		// the observer only follows commit messages to finalize blocks, without voting or keeping
+		// round state, which is all a non-authority needs.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"grandpa-observer",
+			None,
+			sc_finality_grandpa::run_grandpa_observer(grandpa_config, grandpa_link, network)?,
+		);
+	} else if enable_grandpa {
 		// start the full GRANDPA voter
-		// NOTE: non-authorities could run the GRANDPA observer protocol, but at
-		// this point the full voter should provide better guarantees of block
-		// and vote data availability than the observer. The observer has not
-		// been tested extensively yet and having most nodes in a network run it
-		// could lead to finality stalls.
+		// NOTE: non-authorities could run the GRANDPA observer protocol (see
+		// `--grandpa-observer`), but by default the full voter is run since it
+		// provides better guarantees of block and vote data availability than
+		// the observer. Having most nodes in a network run the observer could
+		// lead to finality stalls.
 		let grandpa_config = sc_finality_grandpa::GrandpaParams {
 			config: grandpa_config,
 			link: grandpa_link,
 			network,
 			voting_rule: sc_finality_grandpa::VotingRulesBuilder::default().build(),
 			prometheus_registry,
-			shared_voter_state: SharedVoterState::empty(),
+			shared_voter_state,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 		};
 
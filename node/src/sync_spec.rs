@@ -0,0 +1,98 @@
+//! Chain specs with an embedded checkpoint of the finalized chain.
+//!
+//! Light clients such as smoldot starting from such a chain spec can begin syncing at the
+//! checkpoint instead of at genesis. The checkpoint format includes BABE's epoch changes, so only
+//! nodes built with the `babe` feature can export one.
+
+#[cfg(feature = "babe")]
+use crate::service;
+use sc_cli::{CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+#[cfg(feature = "babe")]
+use sc_service::PartialComponents;
+use sc_service::{error::Error as ServiceError, Configuration};
+use std::io::Write;
+use structopt::StructOpt;
+
+/// The `export-sync-spec` command.
+#[derive(Debug, StructOpt)]
+pub struct ExportSyncSpecCmd {
+	/// Force raw genesis storage output.
+	#[structopt(long)]
+	pub raw: bool,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportSyncSpecCmd {
+	/// Write the chain spec of `config`, with the light sync state taken from the local database,
+	/// to stdout.
+	pub fn run(&self, config: Configuration) -> sc_cli::Result<()> {
+		let json = sync_spec(config, self.raw)?;
+		std::io::stdout().write_all(json.as_bytes()).map_err(Into::into)
+	}
+}
+
+impl CliConfiguration for ExportSyncSpecCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The chain spec of `config` as JSON, embedding the finalized block, the GRANDPA authority set
+/// and the BABE epoch changes of the local database as its light sync state, in the format of the
+/// `sync_state_genSyncSpec` RPC.
+#[cfg(feature = "babe")]
+pub fn sync_spec(config: Configuration, raw: bool) -> Result<String, ServiceError> {
+	use sc_sync_state_rpc::{SyncStateRpcApi, SyncStateRpcHandler};
+
+	// The task manager is kept alive until the checkpoint has been read.
+	let PartialComponents {
+		client,
+		task_manager: _task_manager,
+		other: (_, grandpa_link, consensus_link, _),
+		..
+	} = service::new_partial(&config)?;
+
+	let handler = SyncStateRpcHandler::new(
+		config.chain_spec,
+		client,
+		grandpa_link.shared_authority_set().clone(),
+		consensus_link.epoch_changes().clone(),
+		sc_rpc_api::DenyUnsafe::No,
+	)
+	.map_err(|e| ServiceError::Other(e.to_string()))?;
+	let spec = handler.system_gen_sync_spec(raw).map_err(|e| ServiceError::Other(e.message))?;
+
+	serde_json::to_string_pretty(&spec).map_err(|e| ServiceError::Other(e.to_string()))
+}
+
+/// Aura nodes cannot export a checkpoint: the light sync state format requires the BABE epoch
+/// changes and block weight, which an Aura chain does not have, so no client could read it.
+#[cfg(not(feature = "babe"))]
+pub fn sync_spec(_config: Configuration, _raw: bool) -> Result<String, ServiceError> {
+	Err(ServiceError::Other(AURA_UNSUPPORTED.into()))
+}
+
+/// The error Aura nodes give for `export-sync-spec`.
+#[cfg(not(feature = "babe"))]
+pub const AURA_UNSUPPORTED: &str = "Checkpointed chain specs are only supported with BABE \
+	(`--features babe`). Light clients of Aura chains have to sync from genesis with the raw chain \
+	spec.";
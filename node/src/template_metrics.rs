@@ -0,0 +1,283 @@
+//! Prometheus metrics about `pallet_template` activity.
+//!
+//! The metrics are gathered from the events of every block the node sees finalized, so they
+//! describe the chain as seen by this node rather than its own transactions, and count each block
+//! once even if forks are imported and reverted. Events are decoded with the types of the native
+//! runtime, so blocks whose events no longer decode after a runtime upgrade are logged and counted
+//! instead.
+
+use futures::StreamExt;
+use node_template_runtime::{opaque::Block, Event, Hash, Runtime};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use scale_info::{TypeDef, TypeInfo};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::{
+	codec::Decode,
+	generic::BlockId,
+	traits::{Header as _, One},
+	DispatchError, FixedPointNumber,
+};
+use std::{collections::HashMap, sync::Arc};
+use substrate_prometheus_endpoint::{
+	register, Counter, CounterVec, Gauge, Opts, PrometheusError, Registry, F64, U64,
+};
+
+type EventRecord = frame_system::EventRecord<Event, Hash>;
+
+/// `pallet_template` metrics.
+pub struct Metrics {
+	values_stored: Counter<U64>,
+	values_updated: Counter<U64>,
+	errors: CounterVec<U64>,
+	current_value: Gauge<F64>,
+	decode_failures: Counter<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics with `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			values_stored: register(
+				Counter::new(
+					"template_values_stored_total",
+					"Number of records stored through pallet_template",
+				)?,
+				registry,
+			)?,
+			values_updated: register(
+				Counter::new(
+					"template_values_updated_total",
+					"Number of record updates through pallet_template",
+				)?,
+				registry,
+			)?,
+			errors: register(
+				CounterVec::new(
+					Opts::new(
+						"template_errors_total",
+						"Number of failed pallet_template extrinsics, by error",
+					),
+					&["error"],
+				)?,
+				registry,
+			)?,
+			current_value: register(
+				Gauge::new(
+					"template_current_value",
+					"The value most recently written to the record",
+				)?,
+				registry,
+			)?,
+			decode_failures: register(
+				Counter::new(
+					"template_event_decode_failures_total",
+					"Number of finalized blocks whose events could not be decoded",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Record the encoded events of block `number`.
+	fn record_block(&self, number: u32, events: &[u8], errors: &HashMap<u8, &'static str>) {
+		let events = match Vec::<EventRecord>::decode(&mut &events[..]) {
+			Ok(events) => events,
+			Err(e) => {
+				log::warn!("Failed to decode the events of block #{} for metrics: {}", number, e);
+				self.decode_failures.inc();
+				return
+			},
+		};
+		for record in &events {
+			self.record(&record.event, errors);
+		}
+	}
+
+	fn record(&self, event: &Event, errors: &HashMap<u8, &'static str>) {
+		let value = match event {
+			Event::TemplateModule(pallet_template::Event::SomethingStored(value, _)) => {
+				self.values_stored.inc();
+				value
+			},
+			Event::TemplateModule(pallet_template::Event::SomethingUpdated(_, value, _)) => {
+				self.values_updated.inc();
+				value
+			},
+			Event::System(frame_system::Event::ExtrinsicFailed(
+				DispatchError::Module { index, error, .. },
+				_,
+			)) if *index == template_pallet_index() => {
+				let name = errors.get(error).copied().unwrap_or("Unknown");
+				self.errors.with_label_values(&[name]).inc();
+				return
+			},
+			_ => return,
+		};
+
+		let value = value.into_inner() as f64 / pallet_template::Value::DIV as f64;
+		self.current_value.set(value);
+	}
+}
+
+/// The index of `pallet_template` in the runtime, which module errors refer to.
+fn template_pallet_index() -> u8 {
+	use frame_support::traits::PalletInfo;
+
+	<Runtime as frame_system::Config>::PalletInfo::index::<pallet_template::Pallet<Runtime>>()
+		.expect("pallet_template is part of the runtime; qed") as u8
+}
+
+/// The storage key of the events of `frame_system`, which are not exposed outside the pallet.
+pub(crate) fn events_key() -> StorageKey {
+	use frame_support::traits::PalletInfo;
+
+	let system =
+		<Runtime as frame_system::Config>::PalletInfo::name::<frame_system::Pallet<Runtime>>()
+			.expect("frame_system is part of the runtime; qed");
+	StorageKey(frame_support::storage::storage_prefix(system.as_bytes(), b"Events").to_vec())
+}
+
+/// The names of the `pallet_template` errors by index.
+fn error_names() -> HashMap<u8, &'static str> {
+	match pallet_template::Error::<Runtime>::type_info().type_def() {
+		TypeDef::Variant(def) => def.variants().iter().map(|v| (v.index(), *v.name())).collect(),
+		_ => HashMap::new(),
+	}
+}
+
+/// Update `metrics` from the events of every block finalized by `client`.
+pub async fn run<B, C>(client: Arc<C>, metrics: Metrics)
+where
+	B: Backend<Block>,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + HeaderBackend<Block>,
+{
+	let events_key = events_key();
+	let errors = error_names();
+
+	// A notification may finalize several blocks at once, so the blocks since the last
+	// notification are looked up on the now canonical chain.
+	let mut next = client.info().finalized_number + One::one();
+	let mut finality = client.finality_notification_stream();
+	while let Some(notification) = finality.next().await {
+		let finalized = *notification.header.number();
+		while next <= finalized {
+			let number = next;
+			let hash = match client.hash(number) {
+				Ok(Some(hash)) => hash,
+				_ => break,
+			};
+			next += One::one();
+
+			if let Ok(Some(data)) = client.storage(&BlockId::Hash(hash), &events_key) {
+				metrics.record_block(number, &data.0, &errors);
+			}
+		}
+		next = next.max(finalized + One::one());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_template::Value;
+	use sp_core::crypto::AccountId32;
+	use sp_runtime::codec::Encode;
+
+	fn metrics() -> Metrics {
+		Metrics::register(&Registry::new()).expect("the metrics are only registered once; qed")
+	}
+
+	fn failed(index: u8, error: u8) -> Event {
+		Event::System(frame_system::Event::ExtrinsicFailed(
+			DispatchError::Module { index, error, message: None },
+			Default::default(),
+		))
+	}
+
+	#[test]
+	fn counts_stored_and_updated_values() {
+		let metrics = metrics();
+		let who = AccountId32::new([1; 32]);
+
+		metrics.record(
+			&Event::TemplateModule(pallet_template::Event::SomethingStored(
+				Value::from_inner(3 * Value::DIV / 2),
+				who.clone(),
+			)),
+			&error_names(),
+		);
+		assert_eq!(metrics.values_stored.get(), 1);
+		assert_eq!(metrics.values_updated.get(), 0);
+		assert_eq!(metrics.current_value.get(), 1.5);
+
+		metrics.record(
+			&Event::TemplateModule(pallet_template::Event::SomethingUpdated(
+				Value::from_inner(3 * Value::DIV / 2),
+				Value::from_inner(2 * Value::DIV),
+				who,
+			)),
+			&error_names(),
+		);
+		assert_eq!(metrics.values_stored.get(), 1);
+		assert_eq!(metrics.values_updated.get(), 1);
+		assert_eq!(metrics.current_value.get(), 2.0);
+	}
+
+	#[test]
+	fn counts_template_errors_by_name() {
+		let metrics = metrics();
+		let errors = error_names();
+		let no_value = *errors
+			.iter()
+			.find(|(_, name)| **name == "NoneValue")
+			.expect("NoneValue is a template error; qed")
+			.0;
+
+		metrics.record(&failed(template_pallet_index(), no_value), &errors);
+		metrics.record(&failed(template_pallet_index(), u8::MAX), &errors);
+		assert_eq!(metrics.errors.with_label_values(&["NoneValue"]).get(), 1);
+		assert_eq!(metrics.errors.with_label_values(&["Unknown"]).get(), 1);
+	}
+
+	#[test]
+	fn ignores_errors_of_other_pallets() {
+		let metrics = metrics();
+		let errors = error_names();
+
+		metrics.record(&failed(template_pallet_index().wrapping_add(1), 0), &errors);
+		assert_eq!(metrics.errors.with_label_values(&["NoneValue"]).get(), 0);
+		assert_eq!(metrics.values_stored.get(), 0);
+	}
+
+	#[test]
+	fn counts_blocks_with_undecodable_events() {
+		let metrics = metrics();
+		let errors = error_names();
+		let stored = Event::TemplateModule(pallet_template::Event::SomethingStored(
+			Value::from_inner(Value::DIV),
+			AccountId32::new([1; 32]),
+		));
+		let events = vec![EventRecord {
+			phase: frame_system::Phase::ApplyExtrinsic(0),
+			event: stored,
+			topics: vec![],
+		}]
+		.encode();
+
+		metrics.record_block(1, &events, &errors);
+		assert_eq!(metrics.values_stored.get(), 1);
+		assert_eq!(metrics.decode_failures.get(), 0);
+
+		metrics.record_block(2, &events[..events.len() - 1], &errors);
+		assert_eq!(metrics.values_stored.get(), 1);
+		assert_eq!(metrics.decode_failures.get(), 1);
+	}
+
+	#[test]
+	fn reads_the_events_of_frame_system() {
+		use sp_core::hashing::twox_128;
+
+		assert_eq!(events_key().0, [twox_128(b"System"), twox_128(b"Events")].concat());
+	}
+}
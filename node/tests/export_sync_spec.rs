@@ -0,0 +1,106 @@
+//! The `export-sync-spec` subcommand, which only BABE nodes support.
+
+use std::{
+	path::{Path, PathBuf},
+	process::{Command, Output},
+};
+
+/// A fresh base path for a node of this test.
+fn base_path(name: &str) -> PathBuf {
+	let path =
+		std::env::temp_dir().join(format!("export-sync-spec-{}-{}", name, std::process::id()));
+	let _ = std::fs::remove_dir_all(&path);
+	path
+}
+
+fn export_sync_spec(base_path: &Path) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_node-template"))
+		.args(&["export-sync-spec", "--dev", "--raw", "--base-path"])
+		.arg(base_path)
+		.output()
+		.expect("the node binary is built with the tests; qed")
+}
+
+#[test]
+#[cfg(not(feature = "babe"))]
+fn aura_nodes_refuse_to_export_a_sync_spec() {
+	let base_path = base_path("aura");
+	let output = export_sync_spec(&base_path);
+	let _ = std::fs::remove_dir_all(&base_path);
+
+	assert!(!output.status.success());
+	assert!(output.stdout.is_empty());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("only supported with BABE"));
+}
+
+// Runs a dev node until it finalized a few blocks; run it with `cargo test -- --ignored`.
+#[test]
+#[ignore]
+#[cfg(all(unix, feature = "babe"))]
+fn babe_sync_spec_embeds_the_light_sync_state() {
+	use std::{
+		io::{BufRead, BufReader},
+		net::TcpListener,
+		process::Stdio,
+		sync::mpsc,
+		thread,
+		time::{Duration, Instant},
+	};
+
+	fn free_port() -> String {
+		TcpListener::bind("127.0.0.1:0")
+			.and_then(|listener| listener.local_addr())
+			.expect("the loopback interface is available; qed")
+			.port()
+			.to_string()
+	}
+
+	let base_path = base_path("babe");
+	let mut node = Command::new(env!("CARGO_BIN_EXE_node-template"))
+		.args(&["--dev", "--no-mdns", "--no-prometheus", "--no-telemetry", "--base-path"])
+		.arg(&base_path)
+		.args(&["--port", &free_port(), "--rpc-port", &free_port(), "--ws-port", &free_port()])
+		.stdout(Stdio::null())
+		.stderr(Stdio::piped())
+		.spawn()
+		.expect("the node binary is built with the tests; qed");
+
+	let (tx, rx) = mpsc::channel();
+	let stderr = node.stderr.take().expect("stderr is piped; qed");
+	thread::spawn(move || {
+		for line in BufReader::new(stderr).lines().flatten() {
+			if tx.send(line).is_err() {
+				break
+			}
+		}
+	});
+
+	let deadline = Instant::now() + Duration::from_secs(120);
+	let finalized = loop {
+		let remaining = deadline.saturating_duration_since(Instant::now());
+		match rx.recv_timeout(remaining) {
+			Ok(line) if line.contains("finalized #2") => break true,
+			Ok(_) => continue,
+			Err(_) => break false,
+		}
+	};
+	let _ = node.kill();
+	let _ = node.wait();
+	assert!(finalized, "the dev node did not finalize block #2 in time");
+
+	let output = export_sync_spec(&base_path);
+	let _ = std::fs::remove_dir_all(&base_path);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+	let spec: serde_json::Value =
+		serde_json::from_slice(&output.stdout).expect("the sync spec is JSON; qed");
+	let state = &spec["lightSyncState"];
+	for field in [
+		"finalizedBlockHeader",
+		"babeEpochChanges",
+		"babeFinalizedBlockWeight",
+		"grandpaAuthoritySet",
+	] {
+		assert!(!state[field].is_null(), "lightSyncState lacks {}", field);
+	}
+}
@@ -0,0 +1,121 @@
+//! A local network of two authorities and a full node following finality with the GRANDPA
+//! observer.
+
+use std::{
+	io::{BufRead, BufReader},
+	net::TcpListener,
+	process::{Child, Command, Stdio},
+	sync::mpsc,
+	thread,
+	time::{Duration, Instant},
+};
+
+/// The peer id derived from Alice's `--node-key`.
+const ALICE_PEER_ID: &str = "12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+// The local chain only admits its well-known nodes, which use these keys.
+const ALICE_NODE_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+const BOB_NODE_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+const CHARLIE_NODE_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000003";
+
+/// The number of blocks the observer has to see finalized.
+const FINALIZED_TARGET: u32 = 3;
+const TIMEOUT: Duration = Duration::from_secs(180);
+
+/// A node process that is killed when dropped.
+struct Node(Child);
+
+impl Drop for Node {
+	fn drop(&mut self) {
+		let _ = self.0.kill();
+		let _ = self.0.wait();
+	}
+}
+
+/// A port that was free a moment ago, so that tests running in parallel do not collide.
+fn free_port() -> u16 {
+	TcpListener::bind("127.0.0.1:0")
+		.and_then(|listener| listener.local_addr())
+		.expect("the loopback interface is available; qed")
+		.port()
+}
+
+/// `--port`, `--rpc-port` and `--ws-port` arguments with free ports, the first being `p2p_port`.
+fn port_args(p2p_port: u16) -> Vec<String> {
+	let (rpc, ws) = (free_port(), free_port());
+	["--port", &p2p_port.to_string(), "--rpc-port", &rpc.to_string(), "--ws-port", &ws.to_string()]
+		.iter()
+		.map(|arg| arg.to_string())
+		.collect()
+}
+
+/// Start a `--chain local` node with a throwaway database, listening for peers on `port` and
+/// bootstrapping from Alice at `alice_port`.
+fn start_node(port: u16, alice_port: u16, args: &[&str]) -> Node {
+	let bootnode = format!("/ip4/127.0.0.1/tcp/{}/p2p/{}", alice_port, ALICE_PEER_ID);
+	let child = Command::new(env!("CARGO_BIN_EXE_node-template"))
+		.args(&["--chain", "local", "--tmp", "--no-mdns", "--no-prometheus", "--no-telemetry"])
+		.args(port_args(port))
+		.args(&["--bootnodes", &bootnode])
+		.args(args)
+		.stdout(Stdio::null())
+		.stderr(Stdio::piped())
+		.spawn()
+		.expect("the node binary is built with the tests; qed");
+	Node(child)
+}
+
+/// The finalized block number reported by an informant log line.
+fn finalized_number(line: &str) -> Option<u32> {
+	let rest = &line[line.find("finalized #")? + "finalized #".len()..];
+	let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+	rest[..digits].parse().ok()
+}
+
+// Runs three nodes for up to `TIMEOUT`; run it with `cargo test -- --ignored`.
+#[test]
+#[ignore]
+#[cfg(unix)]
+fn observer_follows_finality() {
+	let alice_port = free_port();
+	let _alice = start_node(alice_port, alice_port, &["--alice", "--node-key", ALICE_NODE_KEY]);
+	let _bob = start_node(free_port(), alice_port, &["--bob", "--node-key", BOB_NODE_KEY]);
+	let mut observer = start_node(
+		free_port(),
+		alice_port,
+		// Observers are no authorities, so they need the offchain worker to find allowed peers.
+		&["--grandpa-observer", "--offchain-worker", "always", "--node-key", CHARLIE_NODE_KEY],
+	);
+
+	let (tx, rx) = mpsc::channel();
+	let stderr = observer.0.stderr.take().expect("stderr is piped; qed");
+	thread::spawn(move || {
+		for line in BufReader::new(stderr).lines().flatten() {
+			if tx.send(line).is_err() {
+				break
+			}
+		}
+	});
+
+	let deadline = Instant::now() + TIMEOUT;
+	loop {
+		let remaining = deadline.saturating_duration_since(Instant::now());
+		let line = rx.recv_timeout(remaining).unwrap_or_else(|_| {
+			panic!("observer did not see block #{} finalized in time", FINALIZED_TARGET)
+		});
+		if finalized_number(&line).map_or(false, |n| n >= FINALIZED_TARGET) {
+			break
+		}
+	}
+}
+
+#[test]
+fn authority_cannot_run_observer() {
+	let status = Command::new(env!("CARGO_BIN_EXE_node-template"))
+		.args(&["--dev", "--tmp", "--no-prometheus", "--no-telemetry", "--grandpa-observer"])
+		.args(port_args(free_port()))
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()
+		.expect("the node binary is built with the tests; qed");
+	assert!(!status.success());
+}
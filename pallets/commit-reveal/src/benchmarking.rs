@@ -0,0 +1,40 @@
+//! Benchmarking setup for pallet-commit-reveal
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as CommitReveal;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Saturating};
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+benchmarks! {
+	commit {
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let hash = CommitReveal::<T>::commitment_hash(&caller, &[1; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), hash)
+	verify {
+		assert!(Commitments::<T>::contains_key(&caller));
+	}
+
+	reveal {
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let hash = CommitReveal::<T>::commitment_hash(&caller, &[1; 32]);
+		CommitReveal::<T>::commit(RawOrigin::Signed(caller.clone()).into(), hash)?;
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number().saturating_add(T::CommitPeriod::get()),
+		);
+	}: _(RawOrigin::Signed(caller.clone()), [1; 32])
+	verify {
+		assert!(!Commitments::<T>::contains_key(&caller));
+	}
+
+	impl_benchmark_test_suite!(CommitReveal, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,264 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Randomness from a commit-reveal scheme.
+///
+/// Time is divided into rounds of `RoundLength` blocks. During the first `CommitPeriod` blocks of
+/// a round participants commit to the hash of a secret, reserving `CommitDeposit`; during the rest
+/// of the round they reveal their secrets and get the deposit back. When the round ends the
+/// revealed secrets are mixed into the random seed, and the deposits of participants who did not
+/// reveal are slashed, which makes withholding a secret to bias the outcome costly.
+///
+/// Unlike `pallet_randomness_collective_flip`, no single block author can predict the seed before
+/// the reveals of a round are in. The scheme is not unbiasable though: the last participant to
+/// reveal sees everyone else's secrets on chain and may withhold theirs at the cost of the deposit,
+/// and block authors may censor reveals. Once a round ended its seed is public, so the
+/// [`Randomness`](frame_support::traits::Randomness) implementation also mixes in the parent block
+/// hash and the extrinsic index, which the sender of a transaction cannot know when signing it. The
+/// author of the block can, so outputs must not decide anything worth more to an author than
+/// skipping a slot.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, OnUnbalanced, Randomness, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, One, Saturating, Zero};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// The secret a participant commits to.
+	pub type Secret = [u8; 32];
+
+	/// A participant's commitment in the current round.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Commitment<T: Config> {
+		/// `T::Hashing` of the `(participant, secret)` pair.
+		pub hash: T::Hash,
+		/// The amount reserved until the secret is revealed.
+		pub deposit: BalanceOf<T>,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which commit deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Where the deposits of participants who fail to reveal go.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The deposit reserved for a commitment.
+		#[pallet::constant]
+		type CommitDeposit: Get<BalanceOf<Self>>;
+
+		/// The length of a round, in blocks.
+		#[pallet::constant]
+		type RoundLength: Get<Self::BlockNumber>;
+
+		/// The number of blocks at the start of each round during which commitments are accepted.
+		/// Secrets are revealed for the rest of the round.
+		#[pallet::constant]
+		type CommitPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of commitments per round.
+		#[pallet::constant]
+		type MaxParticipants: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The unrevealed commitments of the current round.
+	#[pallet::storage]
+	#[pallet::getter(fn commitments)]
+	pub type Commitments<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Commitment<T>, OptionQuery>;
+
+	/// The number of commitments made in the current round, revealed or not.
+	#[pallet::storage]
+	pub type CommitCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The XOR of the secrets revealed in the current round.
+	#[pallet::storage]
+	pub type Accumulator<T> = StorageValue<_, Secret, ValueQuery>;
+
+	/// The number of secrets revealed in the current round.
+	#[pallet::storage]
+	pub type RevealCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The current random seed and the block in which it was fixed.
+	#[pallet::storage]
+	#[pallet::getter(fn seed)]
+	pub type Seed<T: Config> = StorageValue<_, (T::Hash, T::BlockNumber), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A participant committed to a secret. [who]
+		Committed(T::AccountId),
+		/// A participant revealed their secret. [who]
+		Revealed(T::AccountId),
+		/// A participant failed to reveal and their deposit was slashed. [who, amount]
+		Slashed(T::AccountId, BalanceOf<T>),
+		/// A round ended and the seed was updated. [seed, reveals]
+		SeedUpdated(T::Hash, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Commitments are only accepted during the commit period.
+		NotCommitPeriod,
+		/// Secrets are only accepted after the commit period.
+		NotRevealPeriod,
+		/// The account already committed in this round.
+		AlreadyCommitted,
+		/// The round has reached `MaxParticipants` commitments.
+		TooManyParticipants,
+		/// The account cannot afford the commit deposit.
+		InsufficientBalance,
+		/// The account has no unrevealed commitment in this round.
+		NoCommitment,
+		/// The secret does not match the commitment.
+		InvalidReveal,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// End the previous round at the first block of a new one.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if !(now % T::RoundLength::get()).is_zero() {
+				return 0
+			}
+			let commitments = <CommitCount<T>>::get();
+			Self::end_round(now);
+			T::DbWeight::get().reads_writes(4, 5) +
+				T::DbWeight::get().reads_writes(2, 2).saturating_mul(commitments.into())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Commit to a secret for the current round by submitting the `T::Hashing` hash of
+		/// `(who, secret)`, reserving `CommitDeposit`.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn commit(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::in_commit_period(), Error::<T>::NotCommitPeriod);
+			ensure!(!<Commitments<T>>::contains_key(&who), Error::<T>::AlreadyCommitted);
+			let count = <CommitCount<T>>::get();
+			ensure!(count < T::MaxParticipants::get(), Error::<T>::TooManyParticipants);
+
+			let deposit = T::CommitDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			<Commitments<T>>::insert(&who, Commitment { hash, deposit });
+			<CommitCount<T>>::put(count + 1);
+
+			Self::deposit_event(Event::Committed(who));
+			Ok(())
+		}
+
+		/// Reveal the secret committed to in this round and get the deposit back.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn reveal(origin: OriginFor<T>, secret: Secret) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::in_commit_period(), Error::<T>::NotRevealPeriod);
+			let commitment = Self::commitments(&who).ok_or(Error::<T>::NoCommitment)?;
+			ensure!(
+				commitment.hash == Self::commitment_hash(&who, &secret),
+				Error::<T>::InvalidReveal
+			);
+
+			T::Currency::unreserve(&who, commitment.deposit);
+			<Commitments<T>>::remove(&who);
+			<Accumulator<T>>::mutate(|acc| acc.iter_mut().zip(secret).for_each(|(a, s)| *a ^= s));
+			<RevealCount<T>>::mutate(|count| *count += 1);
+
+			Self::deposit_event(Event::Revealed(who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The hash `who` must commit to for `secret`.
+		pub fn commitment_hash(who: &T::AccountId, secret: &Secret) -> T::Hash {
+			T::Hashing::hash_of(&(who, secret))
+		}
+
+		fn in_commit_period() -> bool {
+			let now = <frame_system::Pallet<T>>::block_number();
+			now % T::RoundLength::get() < T::CommitPeriod::get()
+		}
+
+		/// Slash unrevealed commitments and mix the revealed secrets into the seed.
+		fn end_round(now: T::BlockNumber) {
+			for (who, commitment) in <Commitments<T>>::drain() {
+				let (imbalance, _) = T::Currency::slash_reserved(&who, commitment.deposit);
+				T::Slashed::on_unbalanced(imbalance);
+				Self::deposit_event(Event::Slashed(who, commitment.deposit));
+			}
+			<CommitCount<T>>::kill();
+
+			let reveals = <RevealCount<T>>::take();
+			let accumulator = <Accumulator<T>>::take();
+			if reveals == 0 {
+				return
+			}
+
+			let previous = Self::seed().map(|(seed, _)| seed).unwrap_or_default();
+			let seed = T::Hashing::hash_of(&(previous, accumulator));
+			<Seed<T>>::put((seed, now));
+			Self::deposit_event(Event::SeedUpdated(seed, reveals));
+		}
+	}
+
+	impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+		/// Mix `subject`, the parent block hash and the extrinsic index into the current seed.
+		///
+		/// The returned block number is the latest block whose data went into the output: the
+		/// parent block, or the block in which the seed was fixed if that is the current one.
+		/// Before any round completed the seed is zero and only the parent hash is unpredictable.
+		fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+			let (seed, fixed_at) = Self::seed().unwrap_or_default();
+			let parent = <frame_system::Pallet<T>>::block_number().saturating_sub(One::one());
+			let output = T::Hashing::hash_of(&(
+				seed,
+				<frame_system::Pallet<T>>::parent_hash(),
+				<frame_system::Pallet<T>>::extrinsic_index(),
+				subject,
+			));
+			(output, fixed_at.max(parent))
+		}
+	}
+}
@@ -0,0 +1,167 @@
+use crate::{mock::*, Error, Event as CommitRevealEvent, Secret};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnInitialize, Randomness},
+};
+use sp_core::H256;
+
+fn secret(n: u8) -> Secret {
+	[n; 32]
+}
+
+fn commit(who: u64, n: u8) {
+	let hash = CommitReveal::commitment_hash(&who, &secret(n));
+	assert_ok!(CommitReveal::commit(Origin::signed(who), hash));
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		CommitReveal::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn commit_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		assert_eq!(Balances::reserved_balance(1), 1_000);
+		assert_eq!(CommitReveal::commitments(1).unwrap().deposit, 1_000);
+		System::assert_last_event(Event::CommitReveal(CommitRevealEvent::Committed(1)));
+
+		assert_noop!(
+			CommitReveal::commit(Origin::signed(1), H256::zero()),
+			Error::<Test>::AlreadyCommitted
+		);
+	});
+}
+
+#[test]
+fn commit_is_bounded() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		commit(2, 2);
+		commit(3, 3);
+		assert_noop!(
+			CommitReveal::commit(Origin::signed(4), H256::zero()),
+			Error::<Test>::TooManyParticipants
+		);
+	});
+}
+
+#[test]
+fn phases_are_enforced() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(1), secret(1)),
+			Error::<Test>::NotRevealPeriod
+		);
+
+		run_to_block(5);
+		assert_noop!(
+			CommitReveal::commit(Origin::signed(2), H256::zero()),
+			Error::<Test>::NotCommitPeriod
+		);
+		assert_ok!(CommitReveal::reveal(Origin::signed(1), secret(1)));
+	});
+}
+
+#[test]
+fn reveal_must_match_commitment() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		run_to_block(5);
+
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(1), secret(2)),
+			Error::<Test>::InvalidReveal
+		);
+		// Copying someone else's secret does not help, the commitment is bound to the account.
+		assert_noop!(
+			CommitReveal::reveal(Origin::signed(2), secret(1)),
+			Error::<Test>::NoCommitment
+		);
+
+		assert_ok!(CommitReveal::reveal(Origin::signed(1), secret(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(CommitReveal::commitments(1), None);
+		System::assert_last_event(Event::CommitReveal(CommitRevealEvent::Revealed(1)));
+	});
+}
+
+#[test]
+fn round_end_updates_seed_and_slashes_withheld_secrets() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		commit(2, 2);
+		run_to_block(5);
+		assert_ok!(CommitReveal::reveal(Origin::signed(1), secret(1)));
+
+		assert_eq!(CommitReveal::seed(), None);
+		run_to_block(10);
+
+		let (seed, block) = CommitReveal::seed().unwrap();
+		assert_eq!(block, 10);
+		assert_ne!(seed, H256::zero());
+		assert_eq!(Balances::free_balance(1), 10_000);
+		assert_eq!(Balances::total_balance(&2), 9_000);
+		assert_eq!(CommitReveal::commitments(2), None);
+		System::assert_has_event(Event::CommitReveal(CommitRevealEvent::Slashed(2, 1_000)));
+		System::assert_last_event(Event::CommitReveal(CommitRevealEvent::SeedUpdated(seed, 1)));
+
+		// The next round starts from scratch.
+		commit(2, 2);
+	});
+}
+
+#[test]
+fn round_without_reveals_keeps_seed() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		run_to_block(5);
+		assert_ok!(CommitReveal::reveal(Origin::signed(1), secret(1)));
+		run_to_block(10);
+		let seed = CommitReveal::seed();
+
+		run_to_block(20);
+		assert_eq!(CommitReveal::seed(), seed);
+	});
+}
+
+#[test]
+fn random_depends_on_subject_and_seed() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(CommitReveal::random(b"a").1, 0);
+
+		commit(1, 1);
+		run_to_block(5);
+		assert_ok!(CommitReveal::reveal(Origin::signed(1), secret(1)));
+		let before = CommitReveal::random(b"a");
+		run_to_block(10);
+
+		let (a, block) = CommitReveal::random(b"a");
+		assert_eq!(block, 10);
+		assert_ne!(a, before.0);
+		assert_ne!(a, CommitReveal::random(b"b").0);
+	});
+}
+
+#[test]
+fn random_is_not_known_before_inclusion() {
+	new_test_ext().execute_with(|| {
+		commit(1, 1);
+		run_to_block(5);
+		assert_ok!(CommitReveal::reveal(Origin::signed(1), secret(1)));
+		run_to_block(12);
+
+		// Knowing the seed does not tell the output before the including block is known.
+		let first = CommitReveal::random(b"a");
+		assert_eq!(first.1, 11);
+		System::set_parent_hash(H256::repeat_byte(1));
+		let other_parent = CommitReveal::random(b"a");
+		assert_ne!(first.0, other_parent.0);
+		System::set_extrinsic_index(1);
+		assert_ne!(other_parent.0, CommitReveal::random(b"a").0);
+	});
+}
@@ -0,0 +1,40 @@
+//! Benchmarking setup for pallet-disabled-validators
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as DisabledValidators;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_std::prelude::*;
+
+/// Fill the authority set up to `MaxAuthorities`, disable every authority but the last two, and
+/// return the first of those.
+fn all_but_two<T: Config>() -> AuthorityIndex {
+	T::BenchmarkHelper::set_authority_count(T::MaxAuthorities::get());
+	let index = T::MaxAuthorities::get() - 2;
+	let disabled: Vec<AuthorityIndex> = (0..index).collect();
+	Disabled::<T>::put(DisabledOf::<T>::try_from(disabled).expect("within bounds; qed"));
+	index
+}
+
+benchmarks! {
+	disable {
+		let index = all_but_two::<T>();
+		let origin = T::DisableOrigin::successful_origin();
+	}: _<T::Origin>(origin, index)
+	verify {
+		assert!(Disabled::<T>::get().contains(&index));
+	}
+
+	enable {
+		let index = all_but_two::<T>();
+		DisabledValidators::<T>::disable(T::DisableOrigin::successful_origin(), index)?;
+		let origin = T::DisableOrigin::successful_origin();
+	}: _<T::Origin>(origin, index)
+	verify {
+		assert!(!Disabled::<T>::get().contains(&index));
+	}
+
+	impl_benchmark_test_suite!(DisabledValidators, crate::mock::new_test_ext(), crate::mock::Test);
+}
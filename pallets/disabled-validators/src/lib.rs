@@ -0,0 +1,138 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Disabling of misbehaving block authors.
+///
+/// A privileged origin disables authorities by their index in the consensus authority set.
+/// Plugged into the consensus pallet as its `DisabledValidators`, this makes the runtime reject
+/// blocks authored by a disabled authority until it is enabled again. Only indices within the
+/// current set, as reported by `AuthorityCount`, may be disabled, and at least one authority always
+/// stays enabled so the chain cannot halt. Indices refer to positions in the current authority
+/// set, so they should be reviewed whenever that set changes.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::DisabledValidators};
+	use frame_system::pallet_prelude::*;
+
+	/// The index of an authority in the consensus authority set.
+	pub type AuthorityIndex = u32;
+
+	pub type DisabledOf<T> = BoundedVec<AuthorityIndex, <T as Config>::MaxAuthorities>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin allowed to disable and enable authorities.
+		type DisableOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of authorities, which bounds the number of disabled ones.
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+
+		/// The number of authorities in the current consensus authority set.
+		type AuthorityCount: Get<u32>;
+
+		/// Sets up the consensus authority set for the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper;
+	}
+
+	/// Sets up the consensus authority set for the benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper {
+		/// Make `AuthorityCount` report `count` authorities.
+		fn set_authority_count(count: u32);
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The indices of the disabled authorities, kept sorted.
+	#[pallet::storage]
+	#[pallet::getter(fn disabled)]
+	pub type Disabled<T: Config> = StorageValue<_, DisabledOf<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An authority was disabled and can no longer author blocks. [index]
+		AuthorityDisabled(AuthorityIndex),
+		/// A disabled authority was enabled again. [index]
+		AuthorityEnabled(AuthorityIndex),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The index is outside of the current authority set.
+		InvalidIndex,
+		/// The authority is already disabled.
+		AlreadyDisabled,
+		/// The authority is not disabled.
+		NotDisabled,
+		/// The authority is the last enabled one.
+		LastEnabled,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Disable the authority at `index`. Blocks it authors are rejected from the next block on.
+		///
+		/// Fails if `index` is outside of the current authority set or the authority is the last
+		/// enabled one.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn disable(origin: OriginFor<T>, index: AuthorityIndex) -> DispatchResult {
+			T::DisableOrigin::ensure_origin(origin)?;
+			let count = T::AuthorityCount::get().min(T::MaxAuthorities::get());
+			ensure!(index < count, Error::<T>::InvalidIndex);
+
+			<Disabled<T>>::try_mutate(|disabled| -> DispatchResult {
+				let pos =
+					disabled.binary_search(&index).err().ok_or(Error::<T>::AlreadyDisabled)?;
+				// Indices left over from a larger set do not disable anyone.
+				let enabled = count - disabled.iter().filter(|i| **i < count).count() as u32;
+				ensure!(enabled > 1, Error::<T>::LastEnabled);
+				// Indices are unique and below `MaxAuthorities`, so this cannot overflow.
+				disabled.try_insert(pos, index).map_err(|_| Error::<T>::InvalidIndex)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AuthorityDisabled(index));
+			Ok(())
+		}
+
+		/// Allow the authority at `index` to author blocks again.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn enable(origin: OriginFor<T>, index: AuthorityIndex) -> DispatchResult {
+			T::DisableOrigin::ensure_origin(origin)?;
+
+			<Disabled<T>>::try_mutate(|disabled| -> DispatchResult {
+				let pos = disabled.binary_search(&index).map_err(|_| Error::<T>::NotDisabled)?;
+				disabled.remove(pos);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AuthorityEnabled(index));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> DisabledValidators for Pallet<T> {
+		fn is_disabled(index: AuthorityIndex) -> bool {
+			Self::disabled().binary_search(&index).is_ok()
+		}
+	}
+}
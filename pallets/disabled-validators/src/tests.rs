@@ -0,0 +1,81 @@
+use crate::{mock::*, Error, Event as DisabledEvent};
+use frame_support::{assert_noop, assert_ok, error::BadOrigin, traits::DisabledValidators as _};
+
+#[test]
+fn disable_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(DisabledValidators::disable(Origin::signed(1), 0), BadOrigin);
+		assert_noop!(DisabledValidators::enable(Origin::signed(1), 0), BadOrigin);
+	});
+}
+
+#[test]
+fn disable_and_enable() {
+	new_test_ext().execute_with(|| {
+		assert!(!DisabledValidators::is_disabled(2));
+
+		assert_ok!(DisabledValidators::disable(Origin::root(), 2));
+		System::assert_last_event(Event::DisabledValidators(DisabledEvent::AuthorityDisabled(2)));
+		assert!(DisabledValidators::is_disabled(2));
+		assert!(!DisabledValidators::is_disabled(1));
+
+		assert_ok!(DisabledValidators::enable(Origin::root(), 2));
+		System::assert_last_event(Event::DisabledValidators(DisabledEvent::AuthorityEnabled(2)));
+		assert!(!DisabledValidators::is_disabled(2));
+	});
+}
+
+#[test]
+fn disabled_indices_are_sorted() {
+	new_test_ext().execute_with(|| {
+		for index in [3, 0, 2] {
+			assert_ok!(DisabledValidators::disable(Origin::root(), index));
+		}
+		assert_eq!(DisabledValidators::disabled().into_inner(), vec![0, 2, 3]);
+	});
+}
+
+#[test]
+fn disable_rejects_invalid_and_duplicate_indices() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(DisabledValidators::disable(Origin::root(), 4), Error::<Test>::InvalidIndex);
+		assert_ok!(DisabledValidators::disable(Origin::root(), 1));
+		assert_noop!(
+			DisabledValidators::disable(Origin::root(), 1),
+			Error::<Test>::AlreadyDisabled
+		);
+	});
+}
+
+#[test]
+fn disable_is_bounded_by_the_current_authority_set() {
+	new_test_ext().execute_with(|| {
+		AuthorityCount::set(2);
+		assert_noop!(DisabledValidators::disable(Origin::root(), 2), Error::<Test>::InvalidIndex);
+		assert_ok!(DisabledValidators::disable(Origin::root(), 1));
+	});
+}
+
+#[test]
+fn last_enabled_authority_cannot_be_disabled() {
+	new_test_ext().execute_with(|| {
+		for index in 0..3 {
+			assert_ok!(DisabledValidators::disable(Origin::root(), index));
+		}
+		assert_noop!(DisabledValidators::disable(Origin::root(), 3), Error::<Test>::LastEnabled);
+
+		// Indices beyond a shrunk set no longer count as disabled authorities.
+		assert_ok!(DisabledValidators::enable(Origin::root(), 0));
+		AuthorityCount::set(2);
+		assert_noop!(DisabledValidators::disable(Origin::root(), 0), Error::<Test>::LastEnabled);
+		assert_ok!(DisabledValidators::enable(Origin::root(), 1));
+		assert_ok!(DisabledValidators::disable(Origin::root(), 0));
+	});
+}
+
+#[test]
+fn enable_requires_disabled_authority() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(DisabledValidators::enable(Origin::root(), 1), Error::<Test>::NotDisabled);
+	});
+}
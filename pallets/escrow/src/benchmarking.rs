@@ -0,0 +1,64 @@
+//! Benchmarking setup for pallet-escrow
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Escrow;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup, Zero};
+
+fn setup_escrow<T: Config>(payer: T::AccountId) -> (T::AccountId, BalanceOf<T>) {
+	let payee: T::AccountId = account("payee", 0, 0);
+	let amount = T::Currency::minimum_balance() * 100u32.into();
+	T::Currency::make_free_balance_be(&payer, BalanceOf::<T>::max_value() / 2u32.into());
+	Escrow::<T>::create(
+		RawOrigin::Signed(payer).into(),
+		T::Lookup::unlookup(payee.clone()),
+		amount,
+		10u32.into(),
+	)
+	.expect("payer is funded and the deadline lies ahead; qed");
+	(payee, amount)
+}
+
+benchmarks! {
+	create {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let payee: T::AccountId = account("payee", 0, 0);
+		let amount = T::Currency::minimum_balance() * 100u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(payee), amount, 10u32.into())
+	verify {
+		assert_eq!(T::Currency::reserved_balance(&caller), amount);
+	}
+
+	release {
+		let caller: T::AccountId = whitelisted_caller();
+		let (payee, amount) = setup_escrow::<T>(caller.clone());
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(T::Currency::free_balance(&payee), amount);
+	}
+
+	refund {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_escrow::<T>(caller.clone());
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(T::Currency::reserved_balance(&caller).is_zero());
+	}
+
+	arbitrate {
+		let caller: T::AccountId = whitelisted_caller();
+		let (payee, amount) = setup_escrow::<T>(caller);
+		let origin = T::ArbitratorOrigin::successful_origin();
+	}: _<T::Origin>(origin, 0, Resolution::Release)
+	verify {
+		assert_eq!(T::Currency::free_balance(&payee), amount);
+	}
+
+	impl_benchmark_test_suite!(Escrow, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,237 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Conditional payments between a payer and a payee.
+///
+/// Creating an escrow reserves the amount on the payer's account under the [`RESERVE_ID`] named
+/// reserve, so settling it never touches funds other pallets reserved. The payer can release it to
+/// the payee at any time, anyone can send it back to the payer once the deadline has passed, and the
+/// arbitrator origin can settle it either way.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{BalanceStatus, Currency, NamedReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, StaticLookup, Zero};
+
+	/// The named reserve escrowed funds are held in.
+	pub const RESERVE_ID: [u8; 8] = *b"escrow  ";
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Identifier of an escrow.
+	pub type EscrowId = u32;
+
+	/// Funds held between two parties.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Escrow<T: Config> {
+		/// The account the funds are reserved from.
+		pub payer: T::AccountId,
+		/// The account the funds are released to.
+		pub payee: T::AccountId,
+		/// The amount held.
+		pub amount: BalanceOf<T>,
+		/// From this block on the funds may be refunded to the payer.
+		pub deadline: T::BlockNumber,
+	}
+
+	/// How the arbitrator settles an escrow.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum Resolution {
+		/// Pay the payee.
+		Release,
+		/// Return the funds to the payer.
+		Refund,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which escrowed funds are reserved.
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
+
+		/// The origin allowed to settle disputed escrows.
+		type ArbitratorOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The identifier the next escrow will receive.
+	#[pallet::storage]
+	#[pallet::getter(fn next_escrow_id)]
+	pub type NextEscrowId<T> = StorageValue<_, EscrowId, ValueQuery>;
+
+	/// Open escrows.
+	#[pallet::storage]
+	#[pallet::getter(fn escrows)]
+	pub type Escrows<T: Config> = StorageMap<_, Twox64Concat, EscrowId, Escrow<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Funds were put in escrow. [id, payer, payee, amount, deadline]
+		Created(EscrowId, T::AccountId, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// Funds were paid out to the payee. The amount is what was actually moved, which is less
+		/// than the escrowed amount if part of the reserve was slashed. [id, payee, amount]
+		Released(EscrowId, T::AccountId, BalanceOf<T>),
+		/// Funds were returned to the payer. The amount is what was actually unreserved.
+		/// [id, payer, amount]
+		Refunded(EscrowId, T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no escrow with the given id.
+		NotFound,
+		/// Only the payer may release an escrow.
+		NotPayer,
+		/// Escrows must hold a non-zero amount.
+		ZeroAmount,
+		/// The payer and the payee are the same account.
+		SelfEscrow,
+		/// The deadline has to lie in the future.
+		DeadlineInPast,
+		/// The escrow cannot be refunded before its deadline.
+		DeadlineNotReached,
+		/// The payer cannot reserve the amount.
+		InsufficientBalance,
+		/// No more escrow ids are available.
+		Overflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Reserve `amount` from the caller for `payee`, refundable from block `deadline` on.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn create(
+			origin: OriginFor<T>,
+			payee: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let payee = T::Lookup::lookup(payee)?;
+			ensure!(payer != payee, Error::<T>::SelfEscrow);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				deadline > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::DeadlineInPast
+			);
+
+			let id = Self::next_escrow_id();
+			let next = id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			T::Currency::reserve_named(&RESERVE_ID, &payer, amount)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			<NextEscrowId<T>>::put(next);
+			<Escrows<T>>::insert(
+				id,
+				Escrow { payer: payer.clone(), payee: payee.clone(), amount, deadline },
+			);
+
+			Self::deposit_event(Event::Created(id, payer, payee, amount, deadline));
+			Ok(())
+		}
+
+		/// Pay the escrowed funds out to the payee. Only callable by the payer.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn release(origin: OriginFor<T>, id: EscrowId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let escrow = Self::escrows(id).ok_or(Error::<T>::NotFound)?;
+			ensure!(escrow.payer == who, Error::<T>::NotPayer);
+
+			Self::do_release(id, escrow)
+		}
+
+		/// Return the escrowed funds to the payer once the deadline has been reached.
+		///
+		/// Any signed account may trigger the refund.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn refund(origin: OriginFor<T>, id: EscrowId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let escrow = Self::escrows(id).ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= escrow.deadline,
+				Error::<T>::DeadlineNotReached
+			);
+
+			Self::do_refund(id, escrow);
+			Ok(())
+		}
+
+		/// Settle an escrow regardless of its deadline.
+		///
+		/// The dispatch origin must be `ArbitratorOrigin`.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn arbitrate(
+			origin: OriginFor<T>,
+			id: EscrowId,
+			resolution: Resolution,
+		) -> DispatchResult {
+			T::ArbitratorOrigin::ensure_origin(origin)?;
+			let escrow = Self::escrows(id).ok_or(Error::<T>::NotFound)?;
+
+			match resolution {
+				Resolution::Release => Self::do_release(id, escrow),
+				Resolution::Refund => {
+					Self::do_refund(id, escrow);
+					Ok(())
+				},
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn do_release(id: EscrowId, escrow: Escrow<T>) -> DispatchResult {
+			let remainder = T::Currency::repatriate_reserved_named(
+				&RESERVE_ID,
+				&escrow.payer,
+				&escrow.payee,
+				escrow.amount,
+				BalanceStatus::Free,
+			)?;
+			<Escrows<T>>::remove(id);
+
+			let moved = escrow.amount.saturating_sub(remainder);
+			Self::deposit_event(Event::Released(id, escrow.payee, moved));
+			Ok(())
+		}
+
+		fn do_refund(id: EscrowId, escrow: Escrow<T>) {
+			let remainder = T::Currency::unreserve_named(&RESERVE_ID, &escrow.payer, escrow.amount);
+			<Escrows<T>>::remove(id);
+
+			let moved = escrow.amount.saturating_sub(remainder);
+			Self::deposit_event(Event::Refunded(id, escrow.payer, moved));
+		}
+	}
+}
@@ -0,0 +1,161 @@
+use crate::{mock::*, Error, Escrow as EscrowInfo, Event as EscrowEvent, Resolution, RESERVE_ID};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{NamedReservableCurrency, ReservableCurrency},
+};
+use sp_runtime::DispatchError;
+
+const PAYER: u64 = 1;
+const PAYEE: u64 = 2;
+const ARBITRATOR: u64 = 42;
+
+fn create(amount: u128, deadline: u64) {
+	assert_ok!(Escrow::create(Origin::signed(PAYER), PAYEE, amount, deadline));
+}
+
+#[test]
+fn create_reserves_funds() {
+	new_test_ext().execute_with(|| {
+		create(1_000, 10);
+
+		assert_eq!(
+			Escrow::escrows(0),
+			Some(EscrowInfo { payer: PAYER, payee: PAYEE, amount: 1_000, deadline: 10 })
+		);
+		assert_eq!(Escrow::next_escrow_id(), 1);
+		assert_eq!(Balances::reserved_balance(PAYER), 1_000);
+		assert_eq!(Balances::free_balance(PAYER), 9_000);
+		System::assert_last_event(Event::Escrow(EscrowEvent::Created(0, PAYER, PAYEE, 1_000, 10)));
+	});
+}
+
+#[test]
+fn create_validates_input() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Escrow::create(Origin::signed(PAYER), PAYER, 1_000, 10),
+			Error::<Test>::SelfEscrow
+		);
+		assert_noop!(
+			Escrow::create(Origin::signed(PAYER), PAYEE, 0, 10),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			Escrow::create(Origin::signed(PAYER), PAYEE, 1_000, 1),
+			Error::<Test>::DeadlineInPast
+		);
+		assert_noop!(
+			Escrow::create(Origin::signed(3), PAYEE, 1_001, 10),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn payer_releases_to_payee() {
+	new_test_ext().execute_with(|| {
+		create(1_000, 10);
+
+		assert_noop!(Escrow::release(Origin::signed(PAYEE), 0), Error::<Test>::NotPayer);
+		assert_ok!(Escrow::release(Origin::signed(PAYER), 0));
+
+		assert_eq!(Escrow::escrows(0), None);
+		assert_eq!(Balances::reserved_balance(PAYER), 0);
+		assert_eq!(Balances::free_balance(PAYER), 9_000);
+		assert_eq!(Balances::free_balance(PAYEE), 11_000);
+		System::assert_last_event(Event::Escrow(EscrowEvent::Released(0, PAYEE, 1_000)));
+	});
+}
+
+#[test]
+fn release_can_create_payee_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Escrow::create(Origin::signed(PAYER), 5, 1_000, 10));
+		assert_ok!(Escrow::release(Origin::signed(PAYER), 0));
+		assert_eq!(Balances::free_balance(5), 1_000);
+	});
+}
+
+#[test]
+fn refund_only_after_deadline() {
+	new_test_ext().execute_with(|| {
+		create(1_000, 10);
+
+		System::set_block_number(9);
+		assert_noop!(Escrow::refund(Origin::signed(PAYEE), 0), Error::<Test>::DeadlineNotReached);
+
+		System::set_block_number(10);
+		assert_ok!(Escrow::refund(Origin::signed(PAYEE), 0));
+
+		assert_eq!(Escrow::escrows(0), None);
+		assert_eq!(Balances::reserved_balance(PAYER), 0);
+		assert_eq!(Balances::free_balance(PAYER), 10_000);
+		assert_eq!(Balances::free_balance(PAYEE), 10_000);
+		System::assert_last_event(Event::Escrow(EscrowEvent::Refunded(0, PAYER, 1_000)));
+	});
+}
+
+#[test]
+fn arbitrator_settles_either_way() {
+	new_test_ext().execute_with(|| {
+		create(1_000, 10);
+		create(2_000, 10);
+
+		assert_noop!(
+			Escrow::arbitrate(Origin::signed(PAYER), 0, Resolution::Refund),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Escrow::arbitrate(Origin::signed(ARBITRATOR), 0, Resolution::Refund));
+		assert_ok!(Escrow::arbitrate(Origin::signed(ARBITRATOR), 1, Resolution::Release));
+
+		assert_eq!(Balances::reserved_balance(PAYER), 0);
+		assert_eq!(Balances::free_balance(PAYER), 8_000);
+		assert_eq!(Balances::free_balance(PAYEE), 12_000);
+	});
+}
+
+#[test]
+fn settling_reports_the_amount_actually_moved() {
+	new_test_ext().execute_with(|| {
+		create(1_000, 10);
+		create(1_000, 10);
+		Balances::slash_reserved_named(&RESERVE_ID, &PAYER, 1_400);
+
+		assert_ok!(Escrow::release(Origin::signed(PAYER), 0));
+		System::assert_last_event(Event::Escrow(EscrowEvent::Released(0, PAYEE, 600)));
+		assert_eq!(Balances::free_balance(PAYEE), 10_600);
+
+		System::set_block_number(10);
+		assert_ok!(Escrow::refund(Origin::signed(PAYEE), 1));
+		System::assert_last_event(Event::Escrow(EscrowEvent::Refunded(1, PAYER, 0)));
+	});
+}
+
+#[test]
+fn other_reserves_are_left_alone() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::reserve(&PAYER, 500));
+		create(1_000, 10);
+		Balances::slash_reserved_named(&RESERVE_ID, &PAYER, 400);
+
+		assert_ok!(Escrow::release(Origin::signed(PAYER), 0));
+		System::assert_last_event(Event::Escrow(EscrowEvent::Released(0, PAYEE, 600)));
+		assert_eq!(Balances::reserved_balance(PAYER), 500);
+	});
+}
+
+#[test]
+fn settled_escrows_are_gone() {
+	new_test_ext().execute_with(|| {
+		create(1_000, 10);
+		assert_ok!(Escrow::release(Origin::signed(PAYER), 0));
+
+		assert_noop!(Escrow::release(Origin::signed(PAYER), 0), Error::<Test>::NotFound);
+		assert_noop!(Escrow::refund(Origin::signed(PAYER), 0), Error::<Test>::NotFound);
+		assert_noop!(
+			Escrow::arbitrate(Origin::signed(ARBITRATOR), 0, Resolution::Release),
+			Error::<Test>::NotFound
+		);
+	});
+}
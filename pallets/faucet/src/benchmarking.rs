@@ -0,0 +1,42 @@
+//! Benchmarking setup for pallet-faucet
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Faucet;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash, Saturating};
+
+benchmarks! {
+	claim {
+		let dest: T::AccountId = account("dest", 0, 0);
+		T::Currency::make_free_balance_be(
+			&Faucet::<T>::account_id(),
+			BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		Enabled::<T>::put(true);
+		// Exercise the rate limit check with a claim that has just expired.
+		LastClaim::<T>::insert(&dest, T::BlockNumber::from(1u32));
+		let now = T::ClaimPeriod::get().saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		// Claim behind others in the same block, referencing the parent.
+		ClaimsInBlock::<T>::put((now, T::MaxClaimsPerBlock::get().saturating_sub(1)));
+		let at = now - 1u32.into();
+		let hash = T::Hashing::hash(b"parent");
+		frame_system::BlockHash::<T>::insert(at, hash);
+		let nonce = Faucet::<T>::solve(&dest, &hash);
+	}: _(RawOrigin::None, dest.clone(), at, nonce)
+	verify {
+		assert_eq!(T::Currency::free_balance(&dest), T::ClaimAmount::get());
+	}
+
+	set_enabled {
+	}: _(RawOrigin::Root, true)
+	verify {
+		assert!(Enabled::<T>::get());
+	}
+
+	impl_benchmark_test_suite!(Faucet, crate::mock::new_test_ext(), crate::mock::Test);
+}
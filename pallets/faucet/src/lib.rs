@@ -0,0 +1,285 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A faucet for development and test networks.
+///
+/// Any account may receive `ClaimAmount` from the faucet account once every `ClaimPeriod`
+/// blocks. Claims are unsigned, so freshly generated accounts without funds can use them. Since
+/// they cost no fees, each claim carries a proof of work over the destination and a recent block
+/// hash, and at most `MaxClaimsPerBlock` claims are included per block. These limits are checked
+/// when the transaction enters the pool and again on dispatch. The faucet is switched on through
+/// genesis and is meant to stay off on live chains.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AccountIdConversion, Hash, SaturatedConversion, Saturating};
+
+	/// The number of blocks after the referenced block during which a claim's proof of work is
+	/// accepted.
+	pub const PROOF_LIFETIME: u32 = 64;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency handed out by the faucet.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The faucet's pallet id, used to derive the account holding its funds.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The amount transferred by a single claim.
+		#[pallet::constant]
+		type ClaimAmount: Get<BalanceOf<Self>>;
+
+		/// The number of blocks an account has to wait between two claims.
+		#[pallet::constant]
+		type ClaimPeriod: Get<Self::BlockNumber>;
+
+		/// The priority of unsigned claim transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// The number of leading zero bits the proof of work hash of a claim must have.
+		#[pallet::constant]
+		type PowDifficulty: Get<u32>;
+
+		/// The maximum number of claims included in a single block.
+		#[pallet::constant]
+		type MaxClaimsPerBlock: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Whether claims are currently accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn enabled)]
+	pub type Enabled<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// The block in which each account last claimed funds.
+	#[pallet::storage]
+	#[pallet::getter(fn last_claim)]
+	pub type LastClaim<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	/// The number of claims included in a block, and that block's number.
+	#[pallet::storage]
+	pub type ClaimsInBlock<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		/// Whether the faucet accepts claims from genesis onwards.
+		pub enabled: bool,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { enabled: false }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			<Enabled<T>>::put(self.enabled);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Funds were sent to an account. [who, amount]
+		Claimed(T::AccountId, BalanceOf<T>),
+		/// The faucet was switched on or off. [enabled]
+		EnabledSet(bool),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The faucet is switched off.
+		Disabled,
+		/// The account claimed too recently.
+		TooEarly,
+		/// The faucet account cannot cover the claim.
+		FaucetDry,
+		/// The proof of work is invalid, or references an unknown or expired block.
+		BadProof,
+		/// The block already holds `MaxClaimsPerBlock` claims.
+		TooManyClaims,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Send `ClaimAmount` from the faucet to `dest`.
+		///
+		/// This is an unsigned transaction; it is rate limited per destination account and per
+		/// block. `nonce` must be a proof of work for `dest` and the hash of block `at`, which has
+		/// to be one of the last `PROOF_LIFETIME` blocks; see [`Pallet::solve`].
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn claim(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			at: T::BlockNumber,
+			nonce: u64,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::ensure_can_claim(&dest, at, nonce)?;
+
+			let amount = T::ClaimAmount::get();
+			T::Currency::transfer(
+				&Self::account_id(),
+				&dest,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| Error::<T>::FaucetDry)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			<LastClaim<T>>::insert(&dest, now);
+			<ClaimsInBlock<T>>::put((now, Self::claims_in_block() + 1));
+
+			Self::deposit_event(Event::Claimed(dest, amount));
+			Ok(())
+		}
+
+		/// Switch the faucet on or off.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			ensure_root(origin)?;
+			<Enabled<T>>::put(enabled);
+			Self::deposit_event(Event::EnabledSet(enabled));
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (dest, at, nonce) = match call {
+				Call::claim { dest, at, nonce } => (dest, *at, *nonce),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			Self::ensure_can_claim(dest, at, nonce).map_err(|e| match e {
+				Error::<T>::TooEarly => InvalidTransaction::Stale,
+				Error::<T>::FaucetDry => InvalidTransaction::Payment,
+				Error::<T>::BadProof => InvalidTransaction::BadProof,
+				// Block authors keep these in the pool for the next block.
+				Error::<T>::TooManyClaims => InvalidTransaction::ExhaustsResources,
+				_ => InvalidTransaction::Call,
+			})?;
+
+			// The proof is useless once the referenced block is too old.
+			let expires = at.saturating_add(PROOF_LIFETIME.into());
+			let longevity = expires.saturating_sub(<frame_system::Pallet<T>>::block_number());
+
+			ValidTransaction::with_tag_prefix("Faucet")
+				.priority(T::UnsignedPriority::get())
+				// Only one claim per account may sit in the pool at a time.
+				.and_provides(dest)
+				.longevity(longevity.saturated_into::<u64>().max(1))
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the faucet's funds.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		/// Whether `nonce` is a proof of work for a claim to `dest` referencing the block `hash`.
+		pub fn proof_is_valid(dest: &T::AccountId, hash: &T::Hash, nonce: u64) -> bool {
+			let work = T::Hashing::hash_of(&(b"faucet", dest, hash, nonce));
+			leading_zero_bits(work.as_ref()) >= T::PowDifficulty::get()
+		}
+
+		/// Find a proof of work for a claim to `dest` referencing the block `hash`.
+		#[cfg(any(feature = "std", feature = "runtime-benchmarks"))]
+		pub fn solve(dest: &T::AccountId, hash: &T::Hash) -> u64 {
+			(0..)
+				.find(|nonce| Self::proof_is_valid(dest, hash, *nonce))
+				.expect("a proof exists for any difficulty below 64 bits; qed")
+		}
+
+		/// The number of claims included in the current block so far.
+		pub fn claims_in_block() -> u32 {
+			let (block, count) = <ClaimsInBlock<T>>::get();
+			if block == <frame_system::Pallet<T>>::block_number() {
+				count
+			} else {
+				0
+			}
+		}
+
+		fn ensure_can_claim(
+			dest: &T::AccountId,
+			at: T::BlockNumber,
+			nonce: u64,
+		) -> Result<(), Error<T>> {
+			ensure!(Self::enabled(), Error::<T>::Disabled);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				at < now && now.saturating_sub(at) <= PROOF_LIFETIME.into(),
+				Error::<T>::BadProof
+			);
+			let hash = <frame_system::Pallet<T>>::block_hash(at);
+			ensure!(
+				hash != T::Hash::default() && Self::proof_is_valid(dest, &hash, nonce),
+				Error::<T>::BadProof
+			);
+			ensure!(
+				Self::claims_in_block() < T::MaxClaimsPerBlock::get(),
+				Error::<T>::TooManyClaims
+			);
+
+			if let Some(last) = Self::last_claim(dest) {
+				ensure!(now.saturating_sub(last) >= T::ClaimPeriod::get(), Error::<T>::TooEarly);
+			}
+
+			let available = T::Currency::free_balance(&Self::account_id())
+				.saturating_sub(T::Currency::minimum_balance());
+			ensure!(available >= T::ClaimAmount::get(), Error::<T>::FaucetDry);
+			Ok(())
+		}
+	}
+
+	/// The number of leading zero bits of `hash`.
+	fn leading_zero_bits(hash: &[u8]) -> u32 {
+		let mut bits = 0;
+		for byte in hash {
+			bits += byte.leading_zeros();
+			if *byte != 0 {
+				break
+			}
+		}
+		bits
+	}
+}
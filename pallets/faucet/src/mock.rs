@@ -0,0 +1,116 @@
+use crate as pallet_faucet;
+use frame_support::{parameter_types, traits::GenesisBuild, PalletId};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Faucet: pallet_faucet::{Pallet, Call, Storage, Config, Event<T>, ValidateUnsigned},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const FaucetPalletId: PalletId = PalletId(*b"py/fauct");
+	pub const ClaimAmount: u64 = 100;
+	pub const ClaimPeriod: u64 = 10;
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const PowDifficulty: u32 = 8;
+	pub const MaxClaimsPerBlock: u32 = 2;
+}
+
+impl pallet_faucet::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = FaucetPalletId;
+	type ClaimAmount = ClaimAmount;
+	type ClaimPeriod = ClaimPeriod;
+	type UnsignedPriority = UnsignedPriority;
+	type PowDifficulty = PowDifficulty;
+	type MaxClaimsPerBlock = MaxClaimsPerBlock;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(true, 1_000)
+}
+
+/// Build genesis storage with the faucet switched on or off and holding `funds`.
+pub fn new_test_ext_with(enabled: bool, funds: u64) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(Faucet::account_id(), funds)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_faucet::GenesisConfig { enabled }, &mut t)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| set_block(1));
+	ext
+}
+
+/// Move to block `n`, recording a hash for its parent that claims can reference.
+pub fn set_block(n: u64) {
+	System::set_block_number(n);
+	frame_system::BlockHash::<Test>::insert(n - 1, H256::repeat_byte(n as u8));
+}
@@ -0,0 +1,153 @@
+use crate::{mock::*, Call as FaucetCall, Error, Event as FaucetEvent, PROOF_LIFETIME};
+use frame_support::{assert_noop, assert_ok, unsigned::ValidateUnsigned};
+use sp_core::H256;
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+	DispatchError, DispatchResult,
+};
+
+/// A proof of work for a claim to `dest`, referencing the parent block.
+fn proof(dest: u64) -> (u64, u64) {
+	let at = System::block_number() - 1;
+	(at, Faucet::solve(&dest, &System::block_hash(at)))
+}
+
+fn claim(dest: u64) -> DispatchResult {
+	let (at, nonce) = proof(dest);
+	Faucet::claim(Origin::none(), dest, at, nonce)
+}
+
+fn validate_call(dest: u64, at: u64, nonce: u64) -> TransactionValidity {
+	Faucet::validate_unsigned(TransactionSource::External, &FaucetCall::claim { dest, at, nonce })
+}
+
+fn validate(dest: u64) -> TransactionValidity {
+	let (at, nonce) = proof(dest);
+	validate_call(dest, at, nonce)
+}
+
+#[test]
+fn claim_transfers_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(claim(7));
+
+		assert_eq!(Balances::free_balance(7), 100);
+		assert_eq!(Balances::free_balance(Faucet::account_id()), 900);
+		assert_eq!(Faucet::last_claim(7), Some(1));
+		assert_eq!(Faucet::claims_in_block(), 1);
+		System::assert_last_event(Event::Faucet(FaucetEvent::Claimed(7, 100)));
+	});
+}
+
+#[test]
+fn claims_are_unsigned_only() {
+	new_test_ext().execute_with(|| {
+		let (at, nonce) = proof(7);
+		assert_noop!(Faucet::claim(Origin::signed(7), 7, at, nonce), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn claims_are_rate_limited_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(claim(7));
+		assert_noop!(claim(7), Error::<Test>::TooEarly);
+		assert_eq!(validate(7), Err(InvalidTransaction::Stale.into()));
+
+		// Other accounts are unaffected.
+		assert!(validate(8).is_ok());
+
+		set_block(10);
+		assert_noop!(claim(7), Error::<Test>::TooEarly);
+
+		set_block(11);
+		assert!(validate(7).is_ok());
+		assert_ok!(claim(7));
+		assert_eq!(Balances::free_balance(7), 200);
+	});
+}
+
+#[test]
+fn claims_are_capped_per_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(claim(7));
+		assert_ok!(claim(8));
+		assert_noop!(claim(9), Error::<Test>::TooManyClaims);
+		assert_eq!(validate(9), Err(InvalidTransaction::ExhaustsResources.into()));
+
+		set_block(2);
+		assert_eq!(Faucet::claims_in_block(), 0);
+		assert_ok!(claim(9));
+	});
+}
+
+#[test]
+fn invalid_proofs_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let hash = System::block_hash(0);
+		let bad_nonce = (0..).find(|nonce| !Faucet::proof_is_valid(&7, &hash, *nonce)).unwrap();
+		assert_noop!(Faucet::claim(Origin::none(), 7, 0, bad_nonce), Error::<Test>::BadProof);
+		assert_eq!(validate_call(7, 0, bad_nonce), Err(InvalidTransaction::BadProof.into()));
+
+		// The referenced block must be a known ancestor.
+		let (_, nonce) = proof(7);
+		assert_noop!(Faucet::claim(Origin::none(), 7, 1, nonce), Error::<Test>::BadProof);
+		set_block(5);
+		let unknown = Faucet::solve(&7, &H256::default());
+		assert_noop!(Faucet::claim(Origin::none(), 7, 2, unknown), Error::<Test>::BadProof);
+	});
+}
+
+#[test]
+fn proofs_expire() {
+	new_test_ext().execute_with(|| {
+		let (at, nonce) = proof(7);
+		assert_eq!(validate(7).unwrap().longevity, PROOF_LIFETIME as u64 - 1);
+
+		set_block(at + PROOF_LIFETIME as u64);
+		assert_ok!(validate_call(7, at, nonce));
+
+		set_block(at + PROOF_LIFETIME as u64 + 1);
+		assert_noop!(Faucet::claim(Origin::none(), 7, at, nonce), Error::<Test>::BadProof);
+	});
+}
+
+#[test]
+fn validate_unsigned_provides_destination_tag() {
+	new_test_ext().execute_with(|| {
+		let valid = validate(7).unwrap();
+		assert_eq!(valid.priority, UnsignedPriority::get());
+		assert_eq!(valid.provides.len(), 1);
+		assert_ne!(valid.provides, validate(8).unwrap().provides);
+	});
+}
+
+#[test]
+fn disabled_faucet_rejects_claims() {
+	new_test_ext_with(false, 1_000).execute_with(|| {
+		assert_noop!(claim(7), Error::<Test>::Disabled);
+		assert_eq!(validate(7), Err(InvalidTransaction::Call.into()));
+	});
+}
+
+#[test]
+fn root_toggles_faucet() {
+	new_test_ext_with(false, 1_000).execute_with(|| {
+		assert_noop!(Faucet::set_enabled(Origin::signed(1), true), DispatchError::BadOrigin);
+
+		assert_ok!(Faucet::set_enabled(Origin::root(), true));
+		assert_ok!(claim(7));
+
+		assert_ok!(Faucet::set_enabled(Origin::root(), false));
+		assert_noop!(claim(8), Error::<Test>::Disabled);
+	});
+}
+
+#[test]
+fn empty_faucet_rejects_claims() {
+	// Keeping the faucet account alive leaves 99 spendable, one short of a claim.
+	new_test_ext_with(true, 100).execute_with(|| {
+		assert_noop!(claim(7), Error::<Test>::FaucetDry);
+		assert_eq!(validate(7), Err(InvalidTransaction::Payment.into()));
+	});
+}
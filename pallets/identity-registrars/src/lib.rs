@@ -0,0 +1,56 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Genesis registrars for `pallet_identity`.
+///
+/// `pallet_identity` has no genesis config of its own. This pallet adds the configured accounts as
+/// registrars when the chain is built, as if root had called `add_registrar` for each of them, so
+/// the runtime's `RegistrarOrigin` has to accept root.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::RawOrigin;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_identity::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The registrars, in the order of their registrar indices.
+		pub registrars: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { registrars: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for registrar in &self.registrars {
+				pallet_identity::Pallet::<T>::add_registrar(
+					RawOrigin::Root.into(),
+					registrar.clone(),
+				)
+				.expect("genesis registrars must not exceed MaxRegistrars");
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
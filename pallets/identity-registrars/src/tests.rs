@@ -0,0 +1,26 @@
+use crate::mock::*;
+
+fn registrars() -> Vec<Option<u64>> {
+	Identity::registrars().into_iter().map(|r| r.map(|info| info.account)).collect()
+}
+
+#[test]
+fn genesis_registrars_are_added_in_order() {
+	new_test_ext(vec![3, 1]).execute_with(|| {
+		assert_eq!(registrars(), vec![Some(3), Some(1)]);
+		assert_eq!(Identity::registrars()[0].as_ref().unwrap().fee, 0);
+	});
+}
+
+#[test]
+fn no_genesis_registrars_by_default() {
+	new_test_ext(vec![]).execute_with(|| {
+		assert_eq!(registrars(), vec![]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "genesis registrars must not exceed MaxRegistrars")]
+fn too_many_genesis_registrars_are_rejected() {
+	new_test_ext(vec![1, 2, 3]);
+}
@@ -0,0 +1,66 @@
+//! Benchmarking setup for pallet-oracle
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Oracle;
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::TrailingZeroInput;
+use sp_std::prelude::*;
+
+/// An all-zero instance of `V`, which the pallet places no further constraints on.
+fn zero<V: Decode>() -> V {
+	V::decode(&mut TrailingZeroInput::new(&[][..])).expect("infinite input; qed")
+}
+
+/// Fill the member set up to `MaxMembers`, including `caller`.
+fn full_members<T: Config>(caller: &T::AccountId) -> Vec<T::AccountId> {
+	let mut members: Vec<T::AccountId> =
+		(1..T::MaxMembers::get()).map(|i| account("member", i, 0)).collect();
+	members.push(caller.clone());
+	members.sort();
+	Members::<T>::put(MembersOf::<T>::try_from(members.clone()).expect("within bounds; qed"));
+	members
+}
+
+benchmarks! {
+	feed_value {
+		let caller: T::AccountId = whitelisted_caller();
+		let members = full_members::<T>(&caller);
+		let key: T::OracleKey = zero();
+		let value: T::OracleValue = zero();
+		// Every other member already submitted, so the duplicate check scans the full round.
+		for member in members.into_iter().filter(|m| m != &caller) {
+			Oracle::<T>::feed_value(RawOrigin::Signed(member).into(), key.clone(), value)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), key.clone(), value)
+	verify {
+		assert!(Submissions::<T>::get(&key).iter().any(|(who, _)| who == &caller));
+	}
+
+	add_member {
+		let mut members: Vec<T::AccountId> =
+			(1..T::MaxMembers::get()).map(|i| account("member", i, 0)).collect();
+		members.sort();
+		Members::<T>::put(MembersOf::<T>::try_from(members).expect("within bounds; qed"));
+		let who: T::AccountId = account("new", 0, 0);
+		let origin = T::MembershipOrigin::successful_origin();
+	}: _<T::Origin>(origin, who.clone())
+	verify {
+		assert!(Members::<T>::get().contains(&who));
+	}
+
+	remove_member {
+		let who: T::AccountId = whitelisted_caller();
+		full_members::<T>(&who);
+		let origin = T::MembershipOrigin::successful_origin();
+	}: _<T::Origin>(origin, who.clone())
+	verify {
+		assert!(!Members::<T>::get().contains(&who));
+	}
+
+	impl_benchmark_test_suite!(Oracle, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,276 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// An oracle fed by a managed set of members.
+///
+/// Members submit a value per key each round (one block). At the end of the round the submissions
+/// for every key are aggregated into their median, which is robust against a minority of
+/// outliers. Aggregated values expire after `MaxAge` blocks; other pallets read them through the
+/// [`DataProvider`] trait.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+/// Read access to oracle data for pallets that consume it.
+pub trait DataProvider<Key, Value> {
+	/// The current value for `key`, or `None` if there is none or it has gone stale.
+	fn get(key: &Key) -> Option<Value>;
+}
+
+impl<Key, Value> DataProvider<Key, Value> for () {
+	fn get(_key: &Key) -> Option<Value> {
+		None
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::DataProvider;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+	use sp_std::prelude::*;
+
+	pub type MembersOf<T> =
+		BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxMembers>;
+	pub type SubmissionsOf<T> = BoundedVec<
+		(<T as frame_system::Config>::AccountId, <T as Config>::OracleValue),
+		<T as Config>::MaxMembers,
+	>;
+
+	/// An aggregated value together with the block it was aggregated in.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct TimestampedValue<T: Config> {
+		/// The median of the submissions in the round.
+		pub value: T::OracleValue,
+		/// The block in which the round ended.
+		pub updated_at: T::BlockNumber,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The key under which values are fed, e.g. a currency pair.
+		type OracleKey: Parameter + Member + MaxEncodedLen;
+
+		/// The values fed into the oracle.
+		type OracleValue: Parameter + Member + MaxEncodedLen + Copy + Ord;
+
+		/// The origin allowed to add and remove members.
+		type MembershipOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of members.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// The number of submissions a key needs in a round for the round to be aggregated.
+		#[pallet::constant]
+		type MinSubmissions: Get<u32>;
+
+		/// The number of blocks after which an aggregated value is considered stale.
+		#[pallet::constant]
+		type MaxAge: Get<Self::BlockNumber>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The accounts allowed to feed values, kept sorted.
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub type Members<T: Config> = StorageValue<_, MembersOf<T>, ValueQuery>;
+
+	/// The submissions of the current round, by key.
+	#[pallet::storage]
+	pub type Submissions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::OracleKey, SubmissionsOf<T>, ValueQuery>;
+
+	/// The latest aggregated value for each key.
+	#[pallet::storage]
+	#[pallet::getter(fn values)]
+	pub type Values<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::OracleKey, TimestampedValue<T>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The members allowed to feed values from genesis onwards.
+		pub members: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { members: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let mut members = self.members.clone();
+			members.sort();
+			members.dedup();
+			let members: MembersOf<T> =
+				members.try_into().expect("genesis members must not exceed MaxMembers");
+			<Members<T>>::put(members);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A member submitted a value for this round. [who, key, value]
+		ValueFed(T::AccountId, T::OracleKey, T::OracleValue),
+		/// A round ended and its submissions were aggregated. [key, value]
+		ValueAggregated(T::OracleKey, T::OracleValue),
+		/// A round ended with too few submissions to aggregate. [key, submissions]
+		RoundDiscarded(T::OracleKey, u32),
+		/// An account became a member. [who]
+		MemberAdded(T::AccountId),
+		/// An account stopped being a member. [who]
+		MemberRemoved(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Only members may feed values.
+		NotMember,
+		/// The account is already a member.
+		AlreadyMember,
+		/// The member set is full.
+		TooManyMembers,
+		/// The member already submitted a value for this key in the current round.
+		AlreadySubmitted,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Aggregate the round. The weight of this is paid for upfront by `feed_value`, see
+		/// [`Pallet::aggregation_weight`].
+		fn on_finalize(now: T::BlockNumber) {
+			for (key, submissions) in <Submissions<T>>::drain() {
+				let count = submissions.len() as u32;
+				if count < T::MinSubmissions::get() {
+					Self::deposit_event(Event::RoundDiscarded(key, count));
+					continue
+				}
+
+				let value = Self::median(submissions.into_iter().map(|(_, value)| value).collect());
+				<Values<T>>::insert(&key, TimestampedValue { value, updated_at: now });
+				Self::deposit_event(Event::ValueAggregated(key, value));
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit `value` for `key` in the current round. Only callable by members.
+		///
+		/// The weight includes this submission's share of the aggregation at the end of the round.
+		#[pallet::weight(
+			(50_000 as Weight)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+				.saturating_add(Self::aggregation_weight())
+		)]
+		pub fn feed_value(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			value: T::OracleValue,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::members().binary_search(&who).is_ok(), Error::<T>::NotMember);
+
+			<Submissions<T>>::try_mutate(&key, |submissions| -> DispatchResult {
+				ensure!(
+					!submissions.iter().any(|(member, _)| member == &who),
+					Error::<T>::AlreadySubmitted
+				);
+				// A submission per member at most, so this cannot exceed `MaxMembers`.
+				submissions
+					.try_push((who.clone(), value))
+					.map_err(|_| Error::<T>::TooManyMembers)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ValueFed(who, key, value));
+			Ok(())
+		}
+
+		/// Allow `who` to feed values.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::MembershipOrigin::ensure_origin(origin)?;
+
+			<Members<T>>::try_mutate(|members| -> DispatchResult {
+				let index = members.binary_search(&who).err().ok_or(Error::<T>::AlreadyMember)?;
+				members.try_insert(index, who.clone()).map_err(|_| Error::<T>::TooManyMembers)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MemberAdded(who));
+			Ok(())
+		}
+
+		/// Stop `who` from feeding values. Submissions already made this round still count.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::MembershipOrigin::ensure_origin(origin)?;
+
+			<Members<T>>::try_mutate(|members| -> DispatchResult {
+				let index = members.binary_search(&who).map_err(|_| Error::<T>::NotMember)?;
+				members.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MemberRemoved(who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The worst-case weight `on_finalize` spends on a single submission, which is when it is
+		/// the only one for its key: draining the key, storing the aggregated value and the
+		/// submission's share of sorting up to `MaxMembers` values.
+		pub fn aggregation_weight() -> Weight {
+			(10_000 as Weight)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+				.saturating_add((T::MaxMembers::get() as Weight).saturating_mul(1_000))
+		}
+
+		/// The median of a non-empty list of values. For an even number of values the lower of the
+		/// two middle values is taken, so the result is always one of the submissions.
+		fn median(mut values: Vec<T::OracleValue>) -> T::OracleValue {
+			values.sort();
+			values[(values.len() - 1) / 2]
+		}
+	}
+
+	impl<T: Config> DataProvider<T::OracleKey, T::OracleValue> for Pallet<T> {
+		fn get(key: &T::OracleKey) -> Option<T::OracleValue> {
+			let TimestampedValue { value, updated_at } = Self::values(key)?;
+			let age = <frame_system::Pallet<T>>::block_number().saturating_sub(updated_at);
+			(age <= T::MaxAge::get()).then(|| value)
+		}
+	}
+}
@@ -0,0 +1,117 @@
+use crate::{mock::*, DataProvider, Error, Event as OracleEvent, Submissions};
+use frame_support::{assert_noop, assert_ok, traits::OnFinalize, weights::GetDispatchInfo};
+
+const KEY: u32 = 7;
+
+/// Feed `values` for `KEY` from members 1, 2, ... in order and end the round.
+fn round(values: &[u64]) {
+	for (who, value) in values.iter().enumerate() {
+		assert_ok!(Oracle::feed_value(Origin::signed(who as u64 + 1), KEY, *value));
+	}
+	Oracle::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+}
+
+#[test]
+fn genesis_members_are_sorted() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Oracle::members().into_inner(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn feed_value_requires_membership() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Oracle::feed_value(Origin::signed(9), KEY, 100), Error::<Test>::NotMember);
+		assert_ok!(Oracle::feed_value(Origin::signed(1), KEY, 100));
+		System::assert_last_event(Event::Oracle(OracleEvent::ValueFed(1, KEY, 100)));
+	});
+}
+
+#[test]
+fn feed_value_once_per_round() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Oracle::feed_value(Origin::signed(1), KEY, 100));
+		assert_noop!(
+			Oracle::feed_value(Origin::signed(1), KEY, 101),
+			Error::<Test>::AlreadySubmitted
+		);
+		// Other keys are independent.
+		assert_ok!(Oracle::feed_value(Origin::signed(1), KEY + 1, 101));
+	});
+}
+
+#[test]
+fn feed_value_pays_for_its_aggregation() {
+	let call = crate::Call::<Test>::feed_value { key: KEY, value: 100 };
+	assert!(call.get_dispatch_info().weight > Oracle::aggregation_weight());
+	assert!(Oracle::aggregation_weight() >= MaxMembers::get() as u64 * 1_000);
+}
+
+#[test]
+fn round_aggregates_to_median() {
+	new_test_ext().execute_with(|| {
+		round(&[102, 100, 101]);
+		assert_eq!(Oracle::get(&KEY), Some(101));
+		assert_eq!(Oracle::values(KEY).unwrap().updated_at, 1);
+		assert!(!Submissions::<Test>::contains_key(KEY));
+		System::assert_last_event(Event::Oracle(OracleEvent::ValueAggregated(KEY, 101)));
+	});
+}
+
+#[test]
+fn outliers_do_not_move_the_median() {
+	new_test_ext().execute_with(|| {
+		round(&[100, 101, 102, 1_000_000]);
+		assert_eq!(Oracle::get(&KEY), Some(101));
+
+		round(&[0, 100, 101]);
+		assert_eq!(Oracle::get(&KEY), Some(100));
+
+		// Even a lone extreme value on either side only shifts to its neighbour.
+		round(&[u64::MAX, 99, 100, 0]);
+		assert_eq!(Oracle::get(&KEY), Some(99));
+	});
+}
+
+#[test]
+fn round_with_too_few_submissions_is_discarded() {
+	new_test_ext().execute_with(|| {
+		round(&[100, 102]);
+		round(&[5_000]);
+		assert_eq!(Oracle::get(&KEY), Some(100));
+		System::assert_last_event(Event::Oracle(OracleEvent::RoundDiscarded(KEY, 1)));
+	});
+}
+
+#[test]
+fn values_expire_after_max_age() {
+	new_test_ext().execute_with(|| {
+		round(&[100, 100]);
+		System::set_block_number(11);
+		assert_eq!(Oracle::get(&KEY), Some(100));
+		System::set_block_number(12);
+		assert_eq!(Oracle::get(&KEY), None);
+		// The stale value is still stored, only no longer served.
+		assert_eq!(Oracle::values(KEY).unwrap().value, 100);
+	});
+}
+
+#[test]
+fn membership_is_managed_by_membership_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Oracle::add_member(Origin::signed(1), 5),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(Oracle::add_member(Origin::root(), 3), Error::<Test>::AlreadyMember);
+		assert_ok!(Oracle::add_member(Origin::root(), 5));
+		assert_eq!(Oracle::members().into_inner(), vec![1, 2, 3, 4, 5]);
+		assert_noop!(Oracle::add_member(Origin::root(), 6), Error::<Test>::TooManyMembers);
+
+		assert_ok!(Oracle::remove_member(Origin::root(), 2));
+		assert_eq!(Oracle::members().into_inner(), vec![1, 3, 4, 5]);
+		assert_noop!(Oracle::remove_member(Origin::root(), 2), Error::<Test>::NotMember);
+		assert_noop!(Oracle::feed_value(Origin::signed(2), KEY, 100), Error::<Test>::NotMember);
+	});
+}
@@ -0,0 +1,57 @@
+//! Benchmarking setup for pallet-poe
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Poe;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash, StaticLookup};
+use sp_std::vec;
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn claim<T: Config>(owner: T::AccountId) -> T::Hash {
+	let hash = T::Hashing::hash(b"document");
+	let metadata = vec![0u8; T::MaxMetadataLength::get() as usize];
+	Poe::<T>::create_claim(RawOrigin::Signed(owner).into(), hash, metadata)
+		.expect("owner is funded and metadata is within bounds; qed");
+	hash
+}
+
+benchmarks! {
+	create_claim {
+		let m in 0 .. T::MaxMetadataLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let hash = T::Hashing::hash(b"document");
+	}: _(RawOrigin::Signed(caller.clone()), hash, vec![0u8; m as usize])
+	verify {
+		assert_eq!(Claims::<T>::get(&hash).map(|c| c.owner), Some(caller));
+	}
+
+	revoke_claim {
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let hash = claim::<T>(caller.clone());
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(!Claims::<T>::contains_key(&hash));
+	}
+
+	transfer_claim {
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let hash = claim::<T>(caller.clone());
+		let dest: T::AccountId = account("dest", 0, 0);
+		funded::<T>(&dest);
+	}: _(RawOrigin::Signed(caller), hash, T::Lookup::unlookup(dest.clone()))
+	verify {
+		assert_eq!(Claims::<T>::get(&hash).map(|c| c.owner), Some(dest));
+	}
+
+	impl_benchmark_test_suite!(Poe, crate::mock::new_test_ext(), crate::mock::Test);
+}
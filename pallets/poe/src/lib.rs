@@ -0,0 +1,199 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Proof of existence for documents.
+///
+/// Accounts anchor the hash of a document on-chain together with a short piece of metadata. A
+/// deposit is held for as long as the claim exists; owners can revoke claims to get it back or
+/// hand claims over to other accounts.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type MetadataOf<T> = BoundedVec<u8, <T as Config>::MaxMetadataLength>;
+
+	/// A claim on a document hash.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Claim<T: Config> {
+		/// The account holding the claim.
+		pub owner: T::AccountId,
+		/// The block in which the document was first anchored.
+		pub created_at: T::BlockNumber,
+		/// Free-form metadata supplied by the claimant.
+		pub metadata: MetadataOf<T>,
+		/// The amount reserved from `owner` for this claim.
+		pub deposit: BalanceOf<T>,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which claim deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for every claim.
+		#[pallet::constant]
+		type ClaimDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum length of claim metadata, in bytes.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Claims by document hash.
+	#[pallet::storage]
+	#[pallet::getter(fn claims)]
+	pub type Claims<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, Claim<T>>;
+
+	/// Index of the document hashes claimed by each account.
+	#[pallet::storage]
+	pub type ClaimsByOwner<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::Hash, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A document hash was claimed. [who, hash]
+		ClaimCreated(T::AccountId, T::Hash),
+		/// A claim was revoked and its deposit returned. [who, hash]
+		ClaimRevoked(T::AccountId, T::Hash),
+		/// A claim changed hands. [from, to, hash]
+		ClaimTransferred(T::AccountId, T::AccountId, T::Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The document hash has already been claimed.
+		AlreadyClaimed,
+		/// The document hash has not been claimed.
+		NoSuchClaim,
+		/// Only the owner of a claim may revoke or transfer it.
+		NotClaimOwner,
+		/// The metadata is longer than `MaxMetadataLength`.
+		MetadataTooLong,
+		/// The account cannot afford the claim deposit.
+		InsufficientBalance,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim a document hash, reserving `ClaimDeposit` from the caller.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn create_claim(
+			origin: OriginFor<T>,
+			hash: T::Hash,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let metadata: MetadataOf<T> =
+				metadata.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
+			ensure!(!<Claims<T>>::contains_key(&hash), Error::<T>::AlreadyClaimed);
+
+			let deposit = T::ClaimDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			<Claims<T>>::insert(
+				&hash,
+				Claim {
+					owner: who.clone(),
+					created_at: <frame_system::Pallet<T>>::block_number(),
+					metadata,
+					deposit,
+				},
+			);
+			<ClaimsByOwner<T>>::insert(&who, &hash, ());
+
+			Self::deposit_event(Event::ClaimCreated(who, hash));
+			Ok(())
+		}
+
+		/// Revoke a claim owned by the caller and return its deposit.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(1, 3))]
+		pub fn revoke_claim(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let claim = Self::claims(&hash).ok_or(Error::<T>::NoSuchClaim)?;
+			ensure!(claim.owner == who, Error::<T>::NotClaimOwner);
+
+			T::Currency::unreserve(&who, claim.deposit);
+			<Claims<T>>::remove(&hash);
+			<ClaimsByOwner<T>>::remove(&who, &hash);
+
+			Self::deposit_event(Event::ClaimRevoked(who, hash));
+			Ok(())
+		}
+
+		/// Hand a claim over to `dest`, who takes over the deposit.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(1, 5))]
+		pub fn transfer_claim(
+			origin: OriginFor<T>,
+			hash: T::Hash,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			<Claims<T>>::try_mutate(&hash, |maybe_claim| -> DispatchResult {
+				let claim = maybe_claim.as_mut().ok_or(Error::<T>::NoSuchClaim)?;
+				ensure!(claim.owner == who, Error::<T>::NotClaimOwner);
+
+				T::Currency::reserve(&dest, claim.deposit)
+					.map_err(|_| Error::<T>::InsufficientBalance)?;
+				T::Currency::unreserve(&who, claim.deposit);
+				claim.owner = dest.clone();
+				Ok(())
+			})?;
+			<ClaimsByOwner<T>>::remove(&who, &hash);
+			<ClaimsByOwner<T>>::insert(&dest, &hash, ());
+
+			Self::deposit_event(Event::ClaimTransferred(who, dest, hash));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The document hashes claimed by `owner`, with the block each was anchored in.
+		///
+		/// Backs the `PoeApi::claims_of` runtime API.
+		pub fn claims_of(owner: &T::AccountId) -> Vec<(T::Hash, T::BlockNumber)> {
+			<ClaimsByOwner<T>>::iter_prefix(owner)
+				.filter_map(|(hash, ())| Self::claims(&hash).map(|claim| (hash, claim.created_at)))
+				.collect()
+		}
+	}
+}
@@ -0,0 +1,106 @@
+use crate::{mock::*, ClaimsByOwner, Error, Event as PoeEvent};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+
+fn doc(n: u8) -> H256 {
+	H256::repeat_byte(n)
+}
+
+#[test]
+fn create_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(Poe::create_claim(Origin::signed(1), doc(1), b"contract".to_vec()));
+
+		let claim = Poe::claims(doc(1)).unwrap();
+		assert_eq!(claim.owner, 1);
+		assert_eq!(claim.created_at, 5);
+		assert_eq!(claim.metadata.into_inner(), b"contract".to_vec());
+		assert_eq!(claim.deposit, 10);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert!(ClaimsByOwner::<Test>::contains_key(1u64, doc(1)));
+		System::assert_last_event(Event::Poe(PoeEvent::ClaimCreated(1, doc(1))));
+	});
+}
+
+#[test]
+fn create_claim_fails_when_already_claimed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), doc(1), vec![]));
+		assert_noop!(
+			Poe::create_claim(Origin::signed(2), doc(1), vec![]),
+			Error::<Test>::AlreadyClaimed
+		);
+	});
+}
+
+#[test]
+fn create_claim_checks_metadata_and_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Poe::create_claim(Origin::signed(1), doc(1), vec![0; 17]),
+			Error::<Test>::MetadataTooLong
+		);
+		assert_noop!(
+			Poe::create_claim(Origin::signed(3), doc(1), vec![]),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), doc(1), vec![]));
+
+		assert_noop!(Poe::revoke_claim(Origin::signed(2), doc(1)), Error::<Test>::NotClaimOwner);
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), doc(1)));
+
+		assert_eq!(Poe::claims(doc(1)), None);
+		assert!(!ClaimsByOwner::<Test>::contains_key(1u64, doc(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::Poe(PoeEvent::ClaimRevoked(1, doc(1))));
+
+		assert_noop!(Poe::revoke_claim(Origin::signed(1), doc(1)), Error::<Test>::NoSuchClaim);
+	});
+}
+
+#[test]
+fn transfer_claim_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), doc(1), vec![]));
+
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(2), doc(1), 2),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(1), doc(1), 3),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), doc(1), 2));
+
+		assert_eq!(Poe::claims(doc(1)).unwrap().owner, 2);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(Poe::claims_of(&1), vec![]);
+		assert_eq!(Poe::claims_of(&2), vec![(doc(1), 1)]);
+		System::assert_last_event(Event::Poe(PoeEvent::ClaimTransferred(1, 2, doc(1))));
+	});
+}
+
+#[test]
+fn claims_of_lists_owned_claims() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), doc(1), vec![]));
+		System::set_block_number(2);
+		assert_ok!(Poe::create_claim(Origin::signed(1), doc(2), vec![]));
+		assert_ok!(Poe::create_claim(Origin::signed(2), doc(3), vec![]));
+
+		let mut claims = Poe::claims_of(&1);
+		claims.sort();
+		assert_eq!(claims, vec![(doc(1), 1), (doc(2), 2)]);
+		assert_eq!(Poe::claims_of(&2), vec![(doc(3), 1)]);
+		assert_eq!(Poe::claims_of(&3), vec![]);
+	});
+}
@@ -0,0 +1,203 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Per-account transaction rate limiting.
+///
+/// The [`CheckRateLimit`] signed extension counts the transactions each account gets included in
+/// a window of `WindowLength` blocks, and rejects further ones once `MaxTransactions` is reached.
+/// The check also runs when the pool validates transactions, so floods are turned away before
+/// they take up pool space. Valid transactions expire with the window, and rejected ones are
+/// dropped from the pool; they may be submitted again in the next window. Accounts in `Exempt`,
+/// such as the sudo key, are never limited.
+///
+/// The extension has no payload and sits next to `frame_system::CheckNonce`, so clients build
+/// transactions as usual. The counts of past windows are removed in `on_initialize`, at most
+/// `MaxRemovalsPerBlock` per block.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::Contains, weights::DispatchInfo};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{
+		DispatchInfoOf, Dispatchable, One, SignedExtension, UniqueSaturatedInto,
+	};
+	use sp_std::{fmt, marker::PhantomData};
+
+	/// The `InvalidTransaction::Custom` code of transactions over the rate limit.
+	pub const RATE_LIMITED: u8 = 1;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The length of a rate limiting window, in blocks.
+		#[pallet::constant]
+		type WindowLength: Get<Self::BlockNumber>;
+
+		/// The maximum number of transactions an account may get included per window.
+		#[pallet::constant]
+		type MaxTransactions: Get<u32>;
+
+		/// The maximum number of counts of past windows removed per block.
+		#[pallet::constant]
+		type MaxRemovalsPerBlock: Get<u32>;
+
+		/// Accounts that are not rate limited.
+		type Exempt: Contains<Self::AccountId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The number of transactions each account got included, by window. Windows are counted in
+	/// `WindowLength` blocks from genesis.
+	#[pallet::storage]
+	#[pallet::getter(fn usage)]
+	pub type Usage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// The oldest window whose counts may not be removed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn oldest_window)]
+	pub type OldestWindow<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let oldest = Self::oldest_window();
+			if oldest >= Self::current_window() {
+				return T::DbWeight::get().reads(1)
+			}
+
+			// Past windows are no longer read, so their counts are removed a few at a time.
+			match <Usage<T>>::remove_prefix(oldest, Some(T::MaxRemovalsPerBlock::get())) {
+				sp_io::KillStorageResult::AllRemoved(removed) => {
+					<OldestWindow<T>>::put(oldest + One::one());
+					T::DbWeight::get().reads_writes(1, Weight::from(removed) + 1)
+				},
+				sp_io::KillStorageResult::SomeRemaining(removed) =>
+					T::DbWeight::get().reads_writes(1, removed.into()),
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn window_length() -> T::BlockNumber {
+			T::WindowLength::get().max(One::one())
+		}
+
+		/// The index of the window the current block belongs to.
+		pub fn current_window() -> T::BlockNumber {
+			<frame_system::Pallet<T>>::block_number() / Self::window_length()
+		}
+
+		/// The number of blocks left in the current window, including the current block.
+		pub fn blocks_left_in_window() -> T::BlockNumber {
+			let length = Self::window_length();
+			length - <frame_system::Pallet<T>>::block_number() % length
+		}
+
+		/// The number of transactions `who` got included in the current window.
+		pub fn transactions_in_window(who: &T::AccountId) -> u32 {
+			Self::usage(Self::current_window(), who)
+		}
+
+		/// Ensure that `who` may get one more transaction included in the current window.
+		fn ensure_within_limit(who: &T::AccountId) -> Result<(), TransactionValidityError> {
+			if Self::transactions_in_window(who) >= T::MaxTransactions::get() {
+				return Err(InvalidTransaction::Custom(RATE_LIMITED).into())
+			}
+			Ok(())
+		}
+	}
+
+	/// Rejects transactions of accounts that reached `MaxTransactions` in the current window.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct CheckRateLimit<T: Config + Send + Sync>(PhantomData<T>);
+
+	impl<T: Config + Send + Sync> CheckRateLimit<T> {
+		/// Create the extension.
+		pub fn new() -> Self {
+			Self(PhantomData)
+		}
+	}
+
+	impl<T: Config + Send + Sync> Default for CheckRateLimit<T> {
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+
+	impl<T: Config + Send + Sync> fmt::Debug for CheckRateLimit<T> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "CheckRateLimit")
+		}
+	}
+
+	impl<T: Config + Send + Sync> SignedExtension for CheckRateLimit<T>
+	where
+		T::Call: Dispatchable<Info = DispatchInfo>,
+	{
+		const IDENTIFIER: &'static str = "CheckRateLimit";
+		type AccountId = T::AccountId;
+		type Call = T::Call;
+		type AdditionalSigned = ();
+		type Pre = ();
+
+		fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+			Ok(())
+		}
+
+		fn validate(
+			&self,
+			who: &Self::AccountId,
+			_call: &Self::Call,
+			_info: &DispatchInfoOf<Self::Call>,
+			_len: usize,
+		) -> TransactionValidity {
+			if T::Exempt::contains(who) {
+				return Ok(ValidTransaction::default())
+			}
+
+			Pallet::<T>::ensure_within_limit(who)?;
+			let longevity = Pallet::<T>::blocks_left_in_window().unique_saturated_into();
+			Ok(ValidTransaction { longevity, ..Default::default() })
+		}
+
+		fn pre_dispatch(
+			self,
+			who: &Self::AccountId,
+			_call: &Self::Call,
+			info: &DispatchInfoOf<Self::Call>,
+			_len: usize,
+		) -> Result<Self::Pre, TransactionValidityError> {
+			if T::Exempt::contains(who) {
+				return Ok(())
+			}
+
+			Pallet::<T>::ensure_within_limit(who)?;
+			<Usage<T>>::mutate(Pallet::<T>::current_window(), who, |count| {
+				*count = count.saturating_add(1)
+			});
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(1, 1),
+				info.class,
+			);
+			Ok(())
+		}
+	}
+}
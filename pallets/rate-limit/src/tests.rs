@@ -0,0 +1,165 @@
+use crate::{mock::*, CheckRateLimit, OldestWindow, Usage, RATE_LIMITED};
+use frame_support::{
+	assert_ok,
+	traits::OnInitialize,
+	weights::{constants::RocksDbWeight, DispatchInfo},
+};
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionValidityError, ValidTransaction,
+	},
+};
+
+const LIMITED: TransactionValidityError =
+	TransactionValidityError::Invalid(InvalidTransaction::Custom(RATE_LIMITED));
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn valid_transaction(who: u64) -> Result<ValidTransaction, TransactionValidityError> {
+	CheckRateLimit::<Test>::new().validate(&who, &remark(), &DispatchInfo::default(), 0)
+}
+
+fn validate(who: u64) -> Result<(), TransactionValidityError> {
+	valid_transaction(who).map(|_| ())
+}
+
+fn pre_dispatch(who: u64) -> Result<(), TransactionValidityError> {
+	CheckRateLimit::<Test>::new().pre_dispatch(&who, &remark(), &DispatchInfo::default(), 0)
+}
+
+#[test]
+fn pre_dispatch_counts_transactions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(pre_dispatch(1));
+		assert_ok!(pre_dispatch(1));
+		assert_eq!(RateLimit::transactions_in_window(&1), 2);
+		assert_eq!(RateLimit::usage(0, 1), 2);
+	});
+}
+
+#[test]
+fn pre_dispatch_registers_its_weight() {
+	new_test_ext().execute_with(|| {
+		let before = System::block_weight().total();
+		assert_ok!(pre_dispatch(1));
+		let registered = System::block_weight().total() - before;
+		assert_eq!(registered, RocksDbWeight::get().reads_writes(1, 1));
+	});
+}
+
+#[test]
+fn validate_does_not_count_transactions() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MaxTransactions::get() + 1 {
+			assert_ok!(validate(1));
+		}
+		assert_eq!(RateLimit::transactions_in_window(&1), 0);
+	});
+}
+
+#[test]
+fn transactions_over_the_limit_are_rejected() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MaxTransactions::get() {
+			assert_ok!(validate(1));
+			assert_ok!(pre_dispatch(1));
+		}
+
+		assert_eq!(validate(1), Err(LIMITED));
+		assert_eq!(pre_dispatch(1), Err(LIMITED));
+		assert_eq!(RateLimit::transactions_in_window(&1), MaxTransactions::get());
+	});
+}
+
+#[test]
+fn transactions_expire_with_the_window() {
+	new_test_ext().execute_with(|| {
+		let longevity = |block| {
+			System::set_block_number(block);
+			valid_transaction(1).unwrap().longevity
+		};
+		assert_eq!(longevity(1), WindowLength::get() - 1);
+		assert_eq!(longevity(WindowLength::get() - 1), 1);
+		assert_eq!(longevity(WindowLength::get()), WindowLength::get());
+	});
+}
+
+#[test]
+fn accounts_are_limited_independently() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MaxTransactions::get() {
+			assert_ok!(pre_dispatch(1));
+		}
+
+		assert_eq!(validate(1), Err(LIMITED));
+		assert_ok!(validate(2));
+		assert_ok!(pre_dispatch(2));
+	});
+}
+
+#[test]
+fn limit_resets_in_the_next_window() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MaxTransactions::get() {
+			assert_ok!(pre_dispatch(1));
+		}
+
+		System::set_block_number(WindowLength::get() - 1);
+		assert_eq!(validate(1), Err(LIMITED));
+
+		System::set_block_number(WindowLength::get());
+		assert_eq!(RateLimit::transactions_in_window(&1), 0);
+		assert_ok!(validate(1));
+		assert_ok!(pre_dispatch(1));
+		assert_eq!(RateLimit::usage(1, 1), 1);
+	});
+}
+
+#[test]
+fn past_windows_are_removed_a_few_counts_per_block() {
+	let accounts = MaxRemovalsPerBlock::get() as u64 + 1;
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		for who in 1..=accounts {
+			assert_ok!(pre_dispatch(who));
+		}
+	});
+	// Limited removals only count the keys of the backend, not of the overlay.
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		let db = RocksDbWeight::get();
+
+		System::set_block_number(WindowLength::get() - 1);
+		assert_eq!(RateLimit::on_initialize(WindowLength::get() - 1), db.reads(1));
+		assert_eq!(Usage::<Test>::iter_prefix(0).count() as u64, accounts);
+
+		System::set_block_number(WindowLength::get());
+		let removals = MaxRemovalsPerBlock::get() as u64;
+		assert_eq!(RateLimit::on_initialize(WindowLength::get()), db.reads_writes(1, removals));
+		assert_eq!(Usage::<Test>::iter_prefix(0).count(), 1);
+		assert_eq!(RateLimit::oldest_window(), 0);
+
+		System::set_block_number(WindowLength::get() + 1);
+		assert_eq!(RateLimit::on_initialize(WindowLength::get() + 1), db.reads_writes(1, 2));
+		assert_eq!(Usage::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(OldestWindow::<Test>::get(), 1);
+
+		assert_eq!(RateLimit::on_initialize(WindowLength::get() + 1), db.reads(1));
+	});
+}
+
+#[test]
+fn exempt_accounts_are_not_limited() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MaxTransactions::get() * 2 {
+			let valid = valid_transaction(EXEMPT).unwrap();
+			assert_eq!(valid.longevity, TransactionLongevity::max_value());
+			assert_ok!(pre_dispatch(EXEMPT));
+		}
+		assert_eq!(RateLimit::transactions_in_window(&EXEMPT), 0);
+	});
+}
@@ -0,0 +1,66 @@
+//! Benchmarking setup for pallet-registry
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Registry;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_std::{prelude::*, vec};
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn full_entry<T: Config>(owner: T::AccountId) -> (Vec<u8>, Vec<u8>) {
+	let namespace = vec![0u8; T::MaxNamespaceLength::get() as usize];
+	let key = vec![1u8; T::MaxKeyLength::get() as usize];
+	let value = vec![2u8; T::MaxValueLength::get() as usize];
+	Registry::<T>::set(RawOrigin::Signed(owner).into(), namespace.clone(), key.clone(), value)
+		.expect("caller is funded and lengths are within bounds; qed");
+	(namespace, key)
+}
+
+benchmarks! {
+	set {
+		let v in 0 .. T::MaxValueLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let namespace = vec![0u8; T::MaxNamespaceLength::get() as usize];
+		let key = vec![1u8; T::MaxKeyLength::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), namespace.clone(), key.clone(), vec![2u8; v as usize])
+	verify {
+		let namespace: NamespaceOf<T> = namespace.try_into().unwrap();
+		let key: KeyOf<T> = key.try_into().unwrap();
+		assert_eq!(Entries::<T>::get(&namespace, &key).map(|e| e.owner), Some(caller));
+	}
+
+	transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let (namespace, key) = full_entry::<T>(caller.clone());
+		let dest: T::AccountId = account("dest", 0, 0);
+		fund::<T>(&dest);
+		let dest_lookup = T::Lookup::unlookup(dest.clone());
+	}: _(RawOrigin::Signed(caller), namespace.clone(), key.clone(), dest_lookup)
+	verify {
+		let namespace: NamespaceOf<T> = namespace.try_into().unwrap();
+		let key: KeyOf<T> = key.try_into().unwrap();
+		assert_eq!(Entries::<T>::get(&namespace, &key).map(|e| e.owner), Some(dest));
+	}
+
+	remove {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let (namespace, key) = full_entry::<T>(caller.clone());
+	}: _(RawOrigin::Signed(caller), namespace.clone(), key.clone())
+	verify {
+		let namespace: NamespaceOf<T> = namespace.try_into().unwrap();
+		let key: KeyOf<T> = key.try_into().unwrap();
+		assert!(!Entries::<T>::contains_key(&namespace, &key));
+	}
+
+	impl_benchmark_test_suite!(Registry, crate::mock::new_test_ext(), crate::mock::Test);
+}
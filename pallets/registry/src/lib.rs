@@ -0,0 +1,244 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A registry of bounded key-value entries grouped by namespace.
+///
+/// Every entry belongs to the account that created it and is backed by a deposit reserved from
+/// that account. Owners may update, transfer or remove their entries; removing an entry returns
+/// the deposit.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, StaticLookup};
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NamespaceOf<T> = BoundedVec<u8, <T as Config>::MaxNamespaceLength>;
+	pub type KeyOf<T> = BoundedVec<u8, <T as Config>::MaxKeyLength>;
+	pub type ValueOf<T> = BoundedVec<u8, <T as Config>::MaxValueLength>;
+
+	/// A single registry entry.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Entry<T: Config> {
+		/// The account allowed to modify, transfer or remove the entry.
+		pub owner: T::AccountId,
+		/// The stored value.
+		pub value: ValueOf<T>,
+		/// The amount reserved from `owner` for this entry.
+		pub deposit: BalanceOf<T>,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which entry deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The base deposit reserved for every entry.
+		#[pallet::constant]
+		type EntryDeposit: Get<BalanceOf<Self>>;
+
+		/// The additional deposit reserved per byte of key and value.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum length of a namespace, in bytes.
+		#[pallet::constant]
+		type MaxNamespaceLength: Get<u32>;
+
+		/// The maximum length of a key, in bytes.
+		#[pallet::constant]
+		type MaxKeyLength: Get<u32>;
+
+		/// The maximum length of a value, in bytes.
+		#[pallet::constant]
+		type MaxValueLength: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Registry entries, keyed by namespace and then by key so that a whole namespace can be
+	/// iterated with `iter_prefix`.
+	#[pallet::storage]
+	#[pallet::getter(fn entry)]
+	pub type Entries<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, NamespaceOf<T>, Blake2_128Concat, KeyOf<T>, Entry<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new entry was created. [namespace, key, owner, deposit]
+		EntryCreated(NamespaceOf<T>, KeyOf<T>, T::AccountId, BalanceOf<T>),
+		/// The value of an entry was replaced. [namespace, key, owner, deposit]
+		EntryUpdated(NamespaceOf<T>, KeyOf<T>, T::AccountId, BalanceOf<T>),
+		/// An entry changed hands. [namespace, key, from, to]
+		EntryTransferred(NamespaceOf<T>, KeyOf<T>, T::AccountId, T::AccountId),
+		/// An entry was removed and its deposit returned. [namespace, key, owner]
+		EntryRemoved(NamespaceOf<T>, KeyOf<T>, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The namespace is longer than `MaxNamespaceLength`.
+		NamespaceTooLong,
+		/// The key is longer than `MaxKeyLength`.
+		KeyTooLong,
+		/// The value is longer than `MaxValueLength`.
+		ValueTooLong,
+		/// There is no entry under the given namespace and key.
+		NotFound,
+		/// Only the owner of an entry may modify it.
+		NotOwner,
+		/// The account cannot afford the entry deposit.
+		InsufficientBalance,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an entry, or replace the value of an entry owned by the caller.
+		///
+		/// The deposit is topped up or partially returned to match the new value length.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn set(
+			origin: OriginFor<T>,
+			namespace: Vec<u8>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (namespace, key) = Self::bound_path(namespace, key)?;
+			let value: ValueOf<T> = value.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
+			let deposit = Self::deposit_for(&key, &value);
+
+			match <Entries<T>>::get(&namespace, &key) {
+				None => {
+					T::Currency::reserve(&who, deposit)
+						.map_err(|_| Error::<T>::InsufficientBalance)?;
+					<Entries<T>>::insert(
+						&namespace,
+						&key,
+						Entry { owner: who.clone(), value, deposit },
+					);
+					Self::deposit_event(Event::EntryCreated(namespace, key, who, deposit));
+				},
+				Some(old) => {
+					ensure!(old.owner == who, Error::<T>::NotOwner);
+					if deposit > old.deposit {
+						T::Currency::reserve(&who, deposit.saturating_sub(old.deposit))
+							.map_err(|_| Error::<T>::InsufficientBalance)?;
+					} else {
+						T::Currency::unreserve(&who, old.deposit.saturating_sub(deposit));
+					}
+					<Entries<T>>::insert(
+						&namespace,
+						&key,
+						Entry { owner: who.clone(), value, deposit },
+					);
+					Self::deposit_event(Event::EntryUpdated(namespace, key, who, deposit));
+				},
+			}
+			Ok(())
+		}
+
+		/// Hand an entry over to `dest`.
+		///
+		/// `dest` must be able to reserve the entry deposit, after which the deposit held by the
+		/// caller is released.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			namespace: Vec<u8>,
+			key: Vec<u8>,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let (namespace, key) = Self::bound_path(namespace, key)?;
+
+			<Entries<T>>::try_mutate(&namespace, &key, |maybe_entry| -> DispatchResult {
+				let entry = maybe_entry.as_mut().ok_or(Error::<T>::NotFound)?;
+				ensure!(entry.owner == who, Error::<T>::NotOwner);
+
+				T::Currency::reserve(&dest, entry.deposit)
+					.map_err(|_| Error::<T>::InsufficientBalance)?;
+				T::Currency::unreserve(&who, entry.deposit);
+				entry.owner = dest.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::EntryTransferred(namespace, key, who, dest));
+			Ok(())
+		}
+
+		/// Remove an entry owned by the caller and return its deposit.
+		#[pallet::weight(30_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn remove(origin: OriginFor<T>, namespace: Vec<u8>, key: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (namespace, key) = Self::bound_path(namespace, key)?;
+
+			let entry = <Entries<T>>::get(&namespace, &key).ok_or(Error::<T>::NotFound)?;
+			ensure!(entry.owner == who, Error::<T>::NotOwner);
+
+			T::Currency::unreserve(&who, entry.deposit);
+			<Entries<T>>::remove(&namespace, &key);
+
+			Self::deposit_event(Event::EntryRemoved(namespace, key, who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The deposit required for an entry with the given key and value.
+		pub fn deposit_for(key: &KeyOf<T>, value: &ValueOf<T>) -> BalanceOf<T> {
+			let bytes = (key.len() as u32).saturating_add(value.len() as u32);
+			T::ByteDeposit::get()
+				.saturating_mul(bytes.into())
+				.saturating_add(T::EntryDeposit::get())
+		}
+
+		/// All entries stored under `namespace`, in storage order.
+		pub fn entries(namespace: &NamespaceOf<T>) -> Vec<(KeyOf<T>, Entry<T>)> {
+			<Entries<T>>::iter_prefix(namespace).collect()
+		}
+
+		fn bound_path(
+			namespace: Vec<u8>,
+			key: Vec<u8>,
+		) -> Result<(NamespaceOf<T>, KeyOf<T>), Error<T>> {
+			let namespace = namespace.try_into().map_err(|_| Error::<T>::NamespaceTooLong)?;
+			let key = key.try_into().map_err(|_| Error::<T>::KeyTooLong)?;
+			Ok((namespace, key))
+		}
+	}
+}
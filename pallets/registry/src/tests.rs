@@ -0,0 +1,171 @@
+use crate::{mock::*, Entries, Error, Event as RegistryEvent, KeyOf, NamespaceOf};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+
+fn ns() -> Vec<u8> {
+	b"apps".to_vec()
+}
+
+fn namespace(bytes: &[u8]) -> NamespaceOf<Test> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+fn key(bytes: &[u8]) -> KeyOf<Test> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+fn last_event() -> RegistryEvent<Test> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::Registry(e) = r.event { Some(e) } else { None })
+		.last()
+		.unwrap()
+}
+
+#[test]
+fn set_creates_entry_and_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"hello".to_vec()));
+
+		let entry = Registry::entry(namespace(b"apps"), key(b"k")).unwrap();
+		assert_eq!(entry.owner, 1);
+		assert_eq!(entry.value.into_inner(), b"hello".to_vec());
+		// 10 base + 1 byte of key + 5 bytes of value.
+		assert_eq!(entry.deposit, 16);
+		assert_eq!(Balances::reserved_balance(1), 16);
+		assert_eq!(last_event(), RegistryEvent::EntryCreated(namespace(b"apps"), key(b"k"), 1, 16));
+	});
+}
+
+#[test]
+fn update_adjusts_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"hello".to_vec()));
+
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"hello world".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 22);
+		assert_eq!(last_event(), RegistryEvent::EntryUpdated(namespace(b"apps"), key(b"k"), 1, 22));
+
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"hi".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 13);
+	});
+}
+
+#[test]
+fn only_owner_can_update_or_remove() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"v".to_vec()));
+
+		assert_noop!(
+			Registry::set(Origin::signed(2), ns(), b"k".to_vec(), b"x".to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Registry::remove(Origin::signed(2), ns(), b"k".to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Registry::transfer(Origin::signed(2), ns(), b"k".to_vec(), 3),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn lengths_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Registry::set(Origin::signed(1), vec![0; 9], b"k".to_vec(), b"v".to_vec()),
+			Error::<Test>::NamespaceTooLong
+		);
+		assert_noop!(
+			Registry::set(Origin::signed(1), ns(), vec![0; 9], b"v".to_vec()),
+			Error::<Test>::KeyTooLong
+		);
+		assert_noop!(
+			Registry::set(Origin::signed(1), ns(), b"k".to_vec(), vec![0; 17]),
+			Error::<Test>::ValueTooLong
+		);
+	});
+}
+
+#[test]
+fn deposit_must_be_affordable() {
+	new_test_ext().execute_with(|| {
+		// Account 3 has 20 free, and a full entry needs 10 + 8 + 16.
+		assert_noop!(
+			Registry::set(Origin::signed(3), ns(), vec![0; 8], vec![0; 16]),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn transfer_moves_ownership_and_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"v".to_vec()));
+		assert_ok!(Registry::transfer(Origin::signed(1), ns(), b"k".to_vec(), 2));
+
+		assert_eq!(Registry::entry(namespace(b"apps"), key(b"k")).unwrap().owner, 2);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 12);
+		assert_eq!(
+			last_event(),
+			RegistryEvent::EntryTransferred(namespace(b"apps"), key(b"k"), 1, 2)
+		);
+
+		// The new owner is now in control.
+		assert_ok!(Registry::remove(Origin::signed(2), ns(), b"k".to_vec()));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn transfer_fails_when_recipient_cannot_pay() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), vec![0; 16]));
+		assert_ok!(Balances::reserve(&3, 15));
+
+		assert_noop!(
+			Registry::transfer(Origin::signed(1), ns(), b"k".to_vec(), 3),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn remove_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"k".to_vec(), b"v".to_vec()));
+		assert_ok!(Registry::remove(Origin::signed(1), ns(), b"k".to_vec()));
+
+		assert!(!Entries::<Test>::contains_key(namespace(b"apps"), key(b"k")));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(last_event(), RegistryEvent::EntryRemoved(namespace(b"apps"), key(b"k"), 1));
+		assert_noop!(
+			Registry::remove(Origin::signed(1), ns(), b"k".to_vec()),
+			Error::<Test>::NotFound
+		);
+	});
+}
+
+#[test]
+fn entries_iterate_by_namespace() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Registry::set(Origin::signed(1), ns(), b"a".to_vec(), b"1".to_vec()));
+		assert_ok!(Registry::set(Origin::signed(2), ns(), b"b".to_vec(), b"2".to_vec()));
+		assert_ok!(Registry::set(
+			Origin::signed(1),
+			b"other".to_vec(),
+			b"c".to_vec(),
+			b"3".to_vec()
+		));
+
+		let mut keys: Vec<_> = Registry::entries(&namespace(b"apps"))
+			.into_iter()
+			.map(|(k, _)| k.into_inner())
+			.collect();
+		keys.sort();
+		assert_eq!(keys, vec![b"a".to_vec(), b"b".to_vec()]);
+		assert_eq!(Registry::entries(&namespace(b"other")).len(), 1);
+	});
+}
@@ -5,15 +5,57 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Template;
 use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
+use sp_runtime::FixedPointNumber;
+use sp_std::{prelude::*, vec};
+
+fn store<T: Config>(owner: T::AccountId, value: Value) {
+	let name = vec![0u8; T::MaxNameLength::get() as usize];
+	Template::<T>::do_something(RawOrigin::Signed(owner).into(), name, value)
+		.expect("name fits the configured bound; qed");
+}
 
 benchmarks! {
 	do_something {
 		let s in 0 .. 100;
+		let n in 0 .. T::MaxNameLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let value = Value::saturating_from_integer(s);
+	}: _(RawOrigin::Signed(caller), vec![0u8; n as usize], value)
+	verify {
+		assert_eq!(Something::<T>::get().map(|r| r.value), Some(value));
+	}
+
+	update_something {
+		let caller: T::AccountId = whitelisted_caller();
+		store::<T>(caller.clone(), Value::saturating_from_integer(1u32));
+		let value = Value::saturating_from_integer(2u32);
+	}: _(RawOrigin::Signed(caller), value)
+	verify {
+		assert_eq!(Something::<T>::get().map(|r| r.value), Some(value));
+	}
+
+	increment_something {
+		let caller: T::AccountId = whitelisted_caller();
+		store::<T>(caller.clone(), Value::saturating_from_integer(1u32));
+	}: _(RawOrigin::Signed(caller), Value::saturating_from_integer(2u32))
+	verify {
+		assert_eq!(
+			Something::<T>::get().map(|r| r.value),
+			Some(Value::saturating_from_integer(3u32))
+		);
+	}
+
+	decrement_something {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		store::<T>(caller.clone(), Value::saturating_from_integer(3u32));
+	}: _(RawOrigin::Signed(caller), Value::saturating_from_integer(2u32))
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(
+			Something::<T>::get().map(|r| r.value),
+			Some(Value::saturating_from_integer(1u32))
+		);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -18,16 +18,60 @@ mod benchmarking;
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use pallet_oracle::DataProvider;
+	use sp_runtime::{
+		traits::{CheckedAdd, CheckedSub, One},
+		FixedU128,
+	};
+	use sp_std::prelude::*;
+
+	/// The numeric value held by a [`Record`].
+	pub type Value = FixedU128;
+
+	/// A named value together with the account that owns it and the block it last changed in.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Record<T: Config> {
+		/// A short, human readable name for the value.
+		pub name: BoundedVec<u8, T::MaxNameLength>,
+		/// The value itself.
+		pub value: Value,
+		/// The block in which the record was last written.
+		pub updated_at: T::BlockNumber,
+		/// The account allowed to modify the record.
+		pub owner: T::AccountId,
+	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The maximum length of a record name, in bytes.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// The key identifying an oracle feed.
+		type OracleKey: Parameter;
+
+		/// Where `update_from_oracle` reads values from.
+		type Oracle: DataProvider<Self::OracleKey, Value>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
@@ -36,7 +80,7 @@ pub mod pallet {
 	#[pallet::getter(fn something)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	pub type Something<T> = StorageValue<_, Record<T>>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -45,7 +89,9 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		SomethingStored(Value, T::AccountId),
+		/// The stored value was changed by its owner. [old, new, who]
+		SomethingUpdated(Value, Value, T::AccountId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -55,6 +101,14 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// The value would drop below zero.
+		StorageUnderflow,
+		/// The record name is longer than `MaxNameLength`.
+		NameTooLong,
+		/// Only the owner of the record may modify it.
+		NotOwner,
+		/// The oracle has no current value for the requested key.
+		NoOracleValue,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -62,41 +116,100 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
+		/// An example dispatchable that takes a name and a value as parameters, writes them to
 		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
+		///
+		/// The caller becomes the owner of the record. An existing record can only be replaced by
+		/// its owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn do_something(origin: OriginFor<T>, name: Vec<u8>, value: Value) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
 
+			let name: BoundedVec<_, _> = name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			if let Some(record) = <Something<T>>::get() {
+				ensure!(record.owner == who, Error::<T>::NotOwner);
+			}
+
 			// Update storage.
-			<Something<T>>::put(something);
+			<Something<T>>::put(Record {
+				name,
+				value,
+				updated_at: <frame_system::Pallet<T>>::block_number(),
+				owner: who.clone(),
+			});
 
 			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
+			Self::deposit_event(Event::SomethingStored(value, who));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
+		/// Replace the value of the stored record, keeping its name. Only callable by the owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn update_something(origin: OriginFor<T>, value: Value) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::mutate_value(who, |_| Ok(value))
+		}
+
+		/// Add `amount` to the stored value. Only callable by the owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn increment_something(origin: OriginFor<T>, amount: Value) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::mutate_value(who, |old| {
+				old.checked_add(&amount).ok_or(Error::<T>::StorageOverflow)
+			})
+		}
+
+		/// Subtract `amount` from the stored value. Only callable by the owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn decrement_something(origin: OriginFor<T>, amount: Value) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::mutate_value(who, |old| {
+				old.checked_sub(&amount).ok_or(Error::<T>::StorageUnderflow)
+			})
+		}
+
+		/// Replace the stored value with the oracle's current value for `key`. Only callable by the
+		/// owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn update_from_oracle(origin: OriginFor<T>, key: T::OracleKey) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let value = T::Oracle::get(&key).ok_or(Error::<T>::NoOracleValue)?;
+			Self::mutate_value(who, |_| Ok(value))
+		}
+
+		/// An example dispatchable that may throw a custom error. Only callable by the owner.
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match <Something<T>>::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
-					Ok(())
-				},
-			}
+			let who = ensure_signed(origin)?;
+
+			// Increment the stored value; will error if it is not set or in the event of overflow.
+			Self::mutate_value(who, |old| {
+				old.checked_add(&Value::one()).ok_or(Error::<T>::StorageOverflow)
+			})
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Apply `f` to the stored value on behalf of `who`, who must own the record.
+		fn mutate_value(
+			who: T::AccountId,
+			f: impl FnOnce(Value) -> Result<Value, Error<T>>,
+		) -> DispatchResult {
+			<Something<T>>::try_mutate(|maybe_record| -> DispatchResult {
+				let record = maybe_record.as_mut().ok_or(Error::<T>::NoneValue)?;
+				ensure!(record.owner == who, Error::<T>::NotOwner);
+
+				let old = record.value;
+				record.value = f(old)?;
+				record.updated_at = <frame_system::Pallet<T>>::block_number();
+
+				Self::deposit_event(Event::SomethingUpdated(old, record.value, who));
+				Ok(())
+			})
 		}
 	}
 }
@@ -1,10 +1,12 @@
-use crate as pallet_template;
+use crate::{self as pallet_template, Value};
 use frame_support::parameter_types;
 use frame_system as system;
+use pallet_oracle::DataProvider;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	FixedPointNumber,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -25,6 +27,16 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const MaxNameLength: u32 = 8;
+}
+
+/// An oracle reporting `key` itself as the value for every non-zero key.
+pub struct MockOracle;
+
+impl DataProvider<u32, Value> for MockOracle {
+	fn get(key: &u32) -> Option<Value> {
+		(*key != 0).then(|| Value::saturating_from_integer(*key))
+	}
 }
 
 impl system::Config for Test {
@@ -55,6 +67,9 @@ impl system::Config for Test {
 
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type MaxNameLength = MaxNameLength;
+	type OracleKey = u32;
+	type Oracle = MockOracle;
 }
 
 // Build genesis storage according to the mock runtime.
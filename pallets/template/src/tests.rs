@@ -1,13 +1,22 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, Error, Record, Value};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use sp_runtime::FixedPointNumber;
+
+fn v(n: u32) -> Value {
+	Value::saturating_from_integer(n)
+}
+
+fn store(who: u64, value: Value) {
+	assert_ok!(TemplateModule::do_something(Origin::signed(who), b"price".to_vec(), value));
+}
 
 #[test]
 fn it_works_for_default_value() {
 	new_test_ext().execute_with(|| {
 		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		store(1, v(42));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(TemplateModule::something().map(|r| r.value), Some(v(42)));
 	});
 }
 
@@ -18,3 +27,150 @@ fn correct_error_for_none_value() {
 		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
 	});
 }
+
+#[test]
+fn stores_full_record() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(7);
+		store(1, Value::saturating_from_rational(3, 2));
+
+		let record: Record<Test> = TemplateModule::something().unwrap();
+		assert_eq!(record.name.into_inner(), b"price".to_vec());
+		assert_eq!(record.value, Value::saturating_from_rational(3, 2));
+		assert_eq!(record.updated_at, 7);
+		assert_eq!(record.owner, 1);
+	});
+}
+
+#[test]
+fn name_length_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let max = <MaxNameLength as Get<u32>>::get() as usize;
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), vec![0u8; max], v(1)));
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(1), vec![0u8; max + 1], v(1)),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+fn only_owner_can_modify() {
+	new_test_ext().execute_with(|| {
+		store(1, v(1));
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(2), b"other".to_vec(), v(2)),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			TemplateModule::update_something(Origin::signed(2), v(2)),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			TemplateModule::increment_something(Origin::signed(2), v(2)),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			TemplateModule::decrement_something(Origin::signed(2), v(1)),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(TemplateModule::cause_error(Origin::signed(2)), Error::<Test>::NotOwner);
+	});
+}
+
+#[test]
+fn update_and_increment_by_arbitrary_amounts() {
+	new_test_ext().execute_with(|| {
+		store(1, v(10));
+		System::set_block_number(3);
+
+		assert_ok!(TemplateModule::update_something(Origin::signed(1), v(20)));
+		assert_ok!(TemplateModule::increment_something(
+			Origin::signed(1),
+			Value::saturating_from_rational(1, 4)
+		));
+		assert_ok!(TemplateModule::decrement_something(Origin::signed(1), v(5)));
+		assert_eq!(
+			TemplateModule::something().unwrap().value,
+			Value::saturating_from_rational(61, 4)
+		);
+
+		System::set_block_number(4);
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+
+		let record = TemplateModule::something().unwrap();
+		assert_eq!(record.value, Value::saturating_from_rational(65, 4));
+		assert_eq!(record.updated_at, 4);
+		assert_eq!(record.name.into_inner(), b"price".to_vec());
+	});
+}
+
+#[test]
+fn modifying_missing_record_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::update_something(Origin::signed(1), v(1)),
+			Error::<Test>::NoneValue
+		);
+		assert_noop!(
+			TemplateModule::increment_something(Origin::signed(1), v(1)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn increment_overflows_at_max_value() {
+	new_test_ext().execute_with(|| {
+		store(1, Value::from_inner(u128::MAX - Value::DIV));
+
+		assert_ok!(TemplateModule::increment_something(Origin::signed(1), v(1)));
+		assert_eq!(TemplateModule::something().unwrap().value, Value::from_inner(u128::MAX));
+
+		assert_noop!(
+			TemplateModule::increment_something(Origin::signed(1), Value::from_inner(1)),
+			Error::<Test>::StorageOverflow
+		);
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn decrement_underflows_below_zero() {
+	new_test_ext().execute_with(|| {
+		store(1, v(2));
+
+		assert_ok!(TemplateModule::decrement_something(Origin::signed(1), v(2)));
+		assert_eq!(TemplateModule::something().unwrap().value, v(0));
+
+		assert_noop!(
+			TemplateModule::decrement_something(Origin::signed(1), Value::from_inner(1)),
+			Error::<Test>::StorageUnderflow
+		);
+	});
+}
+
+#[test]
+fn update_from_oracle_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::update_from_oracle(Origin::signed(1), 5),
+			Error::<Test>::NoneValue
+		);
+		store(1, v(1));
+
+		assert_noop!(
+			TemplateModule::update_from_oracle(Origin::signed(2), 5),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			TemplateModule::update_from_oracle(Origin::signed(1), 0),
+			Error::<Test>::NoOracleValue
+		);
+		assert_ok!(TemplateModule::update_from_oracle(Origin::signed(1), 5));
+		assert_eq!(TemplateModule::something().map(|r| r.value), Some(v(5)));
+	});
+}
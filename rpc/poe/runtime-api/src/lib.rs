@@ -0,0 +1,18 @@
+//! Runtime API definition for the proof-of-existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// Query claims held in `pallet_poe`.
+	pub trait PoeApi<AccountId, Hash, BlockNumber> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// The document hashes claimed by `owner`, with the block each was anchored in.
+		fn claims_of(owner: AccountId) -> Vec<(Hash, BlockNumber)>;
+	}
+}
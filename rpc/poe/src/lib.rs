@@ -0,0 +1,63 @@
+//! RPC interface for the proof-of-existence pallet.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_poe_rpc_runtime_api::PoeApi as PoeRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, Hash, BlockNumber> {
+	/// The document hashes claimed by `owner`, with the block each was anchored in.
+	#[rpc(name = "poe_claimsOf")]
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(Hash, BlockNumber)>>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+/// Implements the [`PoeApi`] RPC trait by calling into the runtime.
+pub struct Poe<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Poe<C, Block> {
+	/// Create a new `Poe` RPC handler backed by `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Hash, BlockNumber>
+	PoeApi<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber> for Poe<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	AccountId: Codec,
+	Hash: Codec,
+	BlockNumber: Codec,
+{
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(Hash, BlockNumber)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claims_of(&at, owner).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to query claims.".into(),
+			data: Some(e.to_string().into()),
+		})
+	}
+}
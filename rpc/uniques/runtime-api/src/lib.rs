@@ -0,0 +1,18 @@
+//! Runtime API definition for querying `pallet_uniques` ownership.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// Query unique items held in `pallet_uniques`.
+	pub trait UniquesApi<AccountId, ClassId, InstanceId> where
+		AccountId: Codec,
+		ClassId: Codec,
+		InstanceId: Codec,
+	{
+		/// The `(class, instance)` pairs of all items owned by `who`.
+		fn owned(who: AccountId) -> Vec<(ClassId, InstanceId)>;
+	}
+}
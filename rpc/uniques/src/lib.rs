@@ -0,0 +1,59 @@
+//! RPC interface for querying `pallet_uniques` ownership.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_uniques_rpc_runtime_api::UniquesApi as UniquesRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+#[rpc]
+pub trait UniquesApi<BlockHash, AccountId, ClassId, InstanceId> {
+	/// The `(class, instance)` pairs of all items owned by `who`.
+	#[rpc(name = "uniques_owned")]
+	fn owned(&self, who: AccountId, at: Option<BlockHash>) -> Result<Vec<(ClassId, InstanceId)>>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+/// Implements the [`UniquesApi`] RPC trait by calling into the runtime.
+pub struct Uniques<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Uniques<C, Block> {
+	/// Create a new `Uniques` RPC handler backed by `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, ClassId, InstanceId>
+	UniquesApi<<Block as BlockT>::Hash, AccountId, ClassId, InstanceId> for Uniques<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: UniquesRuntimeApi<Block, AccountId, ClassId, InstanceId>,
+	AccountId: Codec,
+	ClassId: Codec,
+	InstanceId: Codec,
+{
+	fn owned(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(ClassId, InstanceId)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.owned(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to query owned items.".into(),
+			data: Some(e.to_string().into()),
+		})
+	}
+}
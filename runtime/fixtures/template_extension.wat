@@ -0,0 +1,53 @@
+;; Forwards its input to the runtime's chain extension and returns the extension's output.
+;;
+;; The first four bytes of the call input select the extension function (little endian); the
+;; remaining bytes are handed to that function as its input. This mirrors what an ink! contract
+;; does when calling a `#[ink::chain_extension]` method.
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 16 16))
+
+	;; [0, 4) size of the input buffer
+	(data (i32.const 0) "\00\40")
+
+	;; [4, 8) size of the output buffer
+	(data (i32.const 4) "\00\40")
+
+	;; [8, 16392) input buffer: func_id followed by the function's input
+
+	;; [16392, 32776) output buffer
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(local.get 0)
+			)
+			(unreachable)
+		)
+	)
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 8) (i32.const 0))
+		(call $assert
+			(i32.ge_u (i32.load (i32.const 0)) (i32.const 4))
+		)
+
+		(drop
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 8))
+				(i32.const 12)
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))
+				(i32.const 16392)
+				(i32.const 4)
+			)
+		)
+
+		(call $seal_return (i32.const 0) (i32.const 16392) (i32.load (i32.const 4)))
+	)
+)
@@ -0,0 +1,62 @@
+//! A chain extension giving smart contracts access to `pallet_template`.
+//!
+//! Contracts invoke the extension through `seal_call_chain_extension`, selecting the function
+//! with one of the `func_id`s below. Writes are dispatched with the contract's own account as the
+//! signed origin, so a contract owns the records it stores just like any other account.
+
+use crate::{Call, Origin, Runtime};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchError, Dispatchable, GetDispatchInfo},
+	traits::Get,
+};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_template::Value;
+use sp_std::prelude::*;
+
+/// Read the value of the stored record.
+///
+/// Takes no input and writes the SCALE-encoded `Option<Value>` to the output buffer.
+pub const READ_SOMETHING: u32 = 1;
+
+/// Store a record on behalf of the calling contract.
+///
+/// Takes a SCALE-encoded `(name: Vec<u8>, value: Value)` tuple and produces no output.
+pub const STORE_SOMETHING: u32 = 2;
+
+/// Exposes reads and writes of `pallet_template` storage to contracts.
+pub struct TemplateExtension;
+
+impl ChainExtension<Runtime> for TemplateExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		match func_id {
+			READ_SOMETHING => {
+				let mut env = env.buf_in_buf_out();
+				env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+				let value = pallet_template::Pallet::<Runtime>::something().map(|r| r.value);
+				env.write(&value.encode(), false, None)?;
+			},
+			STORE_SOMETHING => {
+				let mut env = env.buf_in_buf_out();
+				let input = env.read(env.in_len())?;
+				let (name, value) = <(Vec<u8>, Value)>::decode(&mut &input[..])
+					.map_err(|_| DispatchError::Other("Invalid STORE_SOMETHING input"))?;
+
+				let call =
+					Call::TemplateModule(pallet_template::Call::do_something { name, value });
+				env.charge_weight(call.get_dispatch_info().weight)?;
+				let origin = Origin::signed(env.ext().address().clone());
+				call.dispatch(origin).map_err(|e| e.error)?;
+			},
+			_ => return Err(DispatchError::Other("Unknown chain extension function")),
+		}
+
+		Ok(RetVal::Converging(0))
+	}
+}
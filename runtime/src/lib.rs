@@ -6,6 +6,15 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod chain_extension;
+pub mod priority;
+
+#[cfg(test)]
+mod tests;
+
+use chain_extension::TemplateExtension;
+use frame_support::traits::{Contains, EqualPrivilegeOnly, Get, Nothing};
+use frame_system::EnsureRoot;
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
@@ -14,8 +23,11 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	traits::{
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount, NumberFor,
+		Verify,
+	},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -31,7 +43,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -62,6 +74,15 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// Identifier of a class (collection) of unique items.
+pub type ClassId = u32;
+
+/// Identifier of a unique item within its class.
+pub type InstanceId = u32;
+
+/// Identifier of an oracle feed.
+pub type OracleKey = u32;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -81,6 +102,7 @@ pub mod opaque {
 	impl_opaque_keys! {
 		pub struct SessionKeys {
 			pub aura: Aura,
+			pub babe: Babe,
 			pub grandpa: Grandpa,
 		}
 	}
@@ -98,10 +120,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -121,6 +143,22 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+// NOTE: Currently it is not possible to change the epoch duration after the chain has started.
+//       Attempting to do so will brick block production.
+pub const EPOCH_DURATION_IN_BLOCKS: BlockNumber = 10 * MINUTES;
+// Every slot is expected to produce a block, as `SLOT_DURATION == MILLISECS_PER_BLOCK`.
+pub const EPOCH_DURATION_IN_SLOTS: u64 = EPOCH_DURATION_IN_BLOCKS as u64;
+
+/// The probability of a slot having a primary BABE author, as a fraction.
+pub const PRIMARY_PROBABILITY: (u64, u64) = (1, 4);
+
+/// The BABE epoch configuration at genesis.
+pub const BABE_GENESIS_EPOCH_CONFIG: sp_consensus_babe::BabeEpochConfiguration =
+	sp_consensus_babe::BabeEpochConfiguration {
+		c: PRIMARY_PROBABILITY,
+		allowed_slots: sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryPlainSlots,
+	};
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -199,9 +237,80 @@ parameter_types! {
 	pub const MaxAuthorities: u32 = 32;
 }
 
+/// The size of the authority set of the consensus engine in use. Only one of Aura and BABE has
+/// authorities, depending on how the node was built.
+pub struct AuthorityCount;
+
+impl Get<u32> for AuthorityCount {
+	fn get() -> u32 {
+		Aura::authorities().len().max(Babe::authorities().len()) as u32
+	}
+}
+
+/// Benchmarks disable up to `MaxAuthorities` authorities, more than a dev chain has, so they
+/// rotate in an Aura authority set of the requested size.
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_disabled_validators::BenchmarkHelper for AuthorityCount {
+	fn set_authority_count(count: u32) {
+		use frame_support::traits::OneSessionHandler;
+
+		let keys: Vec<(AccountId, AuraId)> = (0..count)
+			.map(|i| {
+				let seed = [i as u8; 32];
+				(AccountId::new(seed), sp_core::sr25519::Public::from_raw(seed).into())
+			})
+			.collect();
+		let keys = keys.iter().map(|(who, key)| (who, key.clone()));
+		Aura::on_new_session(true, keys.clone(), keys);
+	}
+}
+
+/// Authorities disabled by root can no longer author blocks, under either consensus engine.
+impl pallet_disabled_validators::Config for Runtime {
+	type Event = Event;
+	type DisableOrigin = EnsureRoot<AccountId>;
+	type MaxAuthorities = MaxAuthorities;
+	type AuthorityCount = AuthorityCount;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AuthorityCount;
+}
+
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+	type DisabledValidators = DisabledValidators;
+	type MaxAuthorities = MaxAuthorities;
+}
+
+parameter_types! {
+	pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
+	pub const ExpectedBlockTime: u64 = MILLISECS_PER_BLOCK;
+}
+
+/// Both Aura and BABE are part of the runtime, but only the engine selected by the `babe` feature
+/// drives the slot through `OnTimestampSet`. The runtime must therefore be built with the same
+/// `babe` feature as the node that runs it.
+impl pallet_babe::Config for Runtime {
+	type EpochDuration = EpochDuration;
+	type ExpectedBlockTime = ExpectedBlockTime;
+	// There is no session pallet, so the authorities set at genesis are kept for every epoch.
+	type EpochChangeTrigger = pallet_babe::SameAuthoritiesForever;
+	type DisabledValidators = DisabledValidators;
+
+	type KeyOwnerProofSystem = ();
+
+	type KeyOwnerProof = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		pallet_babe::AuthorityId,
+	)>>::Proof;
+
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		pallet_babe::AuthorityId,
+	)>>::IdentificationTuple;
+
+	type HandleEquivocation = ();
+
+	type WeightInfo = ();
 	type MaxAuthorities = MaxAuthorities;
 }
 
@@ -232,7 +341,10 @@ parameter_types! {
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
+	#[cfg(not(feature = "babe"))]
 	type OnTimestampSet = Aura;
+	#[cfg(feature = "babe")]
+	type OnTimestampSet = Babe;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
 }
@@ -240,11 +352,12 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -269,14 +382,335 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ();
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 1_000_000_000;
+	pub const ApprovalDeposit: Balance = 1_000_000;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10_000_000;
+	pub const MetadataDepositPerByte: Balance = 100_000;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	/// Root may create, destroy and reconfigure any asset.
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Fungibles = Assets;
+	/// Fees paid in an asset are converted at the ratio of the asset's minimum balance to the
+	/// native existential deposit, and burned like native fees.
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+		(),
+	>;
+}
+
+parameter_types! {
+	pub const ClassDeposit: Balance = 1_000_000_000;
+	pub const InstanceDeposit: Balance = 10_000_000;
+	pub const UniquesMetadataDepositBase: Balance = 10_000_000;
+	pub const AttributeDepositBase: Balance = 10_000_000;
+	pub const UniquesDepositPerByte: Balance = 100_000;
+	pub const UniquesStringLimit: u32 = 128;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 256;
+}
+
+impl pallet_uniques::Config for Runtime {
+	type Event = Event;
+	type ClassId = ClassId;
+	type InstanceId = InstanceId;
+	type Currency = Balances;
+	/// Root may create classes without a deposit and take over existing ones.
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = UniquesDepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Placed into a contract's account on instantiation, on top of the existential deposit.
+	pub const ContractDeposit: Balance = 1_000_000_000;
+	pub const DeletionQueueDepth: u32 = 128;
+	/// Lazy deletion of terminated contracts runs in `on_initialize`; cap it at a tenth of a block.
+	pub DeletionWeightLimit: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+}
+
+impl pallet_contracts::Config for Runtime {
+	type Time = Timestamp;
+	type Randomness = CommitReveal;
+	type Currency = Balances;
+	type Event = Event;
+	type Call = Call;
+	/// Contracts may not dispatch runtime calls directly; they go through the chain extension.
+	type CallFilter = Nothing;
+	type ContractDeposit = ContractDeposit;
+	type CallStack = [pallet_contracts::Frame<Self>; 31];
+	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
+	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
+	type ChainExtension = TemplateExtension;
+	type DeletionQueueDepth = DeletionQueueDepth;
+	type DeletionWeightLimit = DeletionWeightLimit;
+	type Schedule = Schedule;
+}
+
+parameter_types! {
+	pub const BasicDeposit: Balance = 1_000_000_000;
+	pub const FieldDeposit: Balance = 250_000_000;
+	pub const SubAccountDeposit: Balance = 200_000_000;
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	/// Deposits slashed from misbehaving identities are burned.
+	type Slashed = ();
+	/// The sudo key may kill identities and add registrars. Registrars for a new chain are set
+	/// in the genesis config of `pallet_identity_registrars`, which adds them as root.
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type RegistrarOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_identity_registrars::Config for Runtime {}
+
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096 * 1024;
+	pub const PreimageBaseDeposit: Balance = 1_000_000_000;
+	pub const PreimageByteDeposit: Balance = 1_000_000;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Event = Event;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+	pub const NoPreimagePostponement: Option<BlockNumber> = Some(10);
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
+}
+
 impl pallet_sudo::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const RateLimitWindow: BlockNumber = MINUTES;
+	pub const MaxTransactionsPerWindow: u32 = 100;
+	pub const RateLimitMaxRemovals: u32 = 100;
+}
+
+/// The sudo key, which must stay able to intervene while the chain is flooded.
+pub struct SudoKey;
+
+impl Contains<AccountId> for SudoKey {
+	fn contains(who: &AccountId) -> bool {
+		Sudo::key() == *who
+	}
+}
+
+impl pallet_rate_limit::Config for Runtime {
+	type WindowLength = RateLimitWindow;
+	type MaxTransactions = MaxTransactionsPerWindow;
+	type MaxRemovalsPerBlock = RateLimitMaxRemovals;
+	type Exempt = SudoKey;
+}
+
+parameter_types! {
+	pub const MaxWellKnownNodes: u32 = 16;
+	pub const MaxPeerIdLength: u32 = 128;
+}
+
+/// Only well-known nodes and the connections their owners allow may join the network. The node
+/// only accepts reserved peers, and the pallet's offchain worker makes the allow-list its reserved
+/// peers wherever offchain workers are enabled.
+impl pallet_node_authorization::Config for Runtime {
+	type Event = Event;
+	type MaxWellKnownNodes = MaxWellKnownNodes;
+	type MaxPeerIdLength = MaxPeerIdLength;
+	type AddOrigin = EnsureRoot<AccountId>;
+	type RemoveOrigin = EnsureRoot<AccountId>;
+	type SwapOrigin = EnsureRoot<AccountId>;
+	type ResetOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const OracleMaxMembers: u32 = 16;
+	pub const OracleMinSubmissions: u32 = 3;
+	pub const OracleMaxAge: BlockNumber = 10 * MINUTES;
+}
+
+impl pallet_oracle::Config for Runtime {
+	type Event = Event;
+	type OracleKey = OracleKey;
+	/// Oracle values share the template's fixed-point type so it can consume them directly.
+	type OracleValue = pallet_template::Value;
+	type MembershipOrigin = EnsureRoot<AccountId>;
+	type MaxMembers = OracleMaxMembers;
+	type MinSubmissions = OracleMinSubmissions;
+	type MaxAge = OracleMaxAge;
+}
+
+parameter_types! {
+	pub const CommitDeposit: Balance = 1_000_000_000;
+	pub const RandomnessRoundLength: BlockNumber = 10 * MINUTES;
+	pub const RandomnessCommitPeriod: BlockNumber = 5 * MINUTES;
+	pub const MaxRandomnessParticipants: u32 = 64;
+}
+
+// The randomness is consumed by contracts through `seal_random`. `pallet_template` only stores
+// values chosen by their writers, so it has no `Randomness` type; a pallet that needs one should
+// take `CommitReveal` rather than `RandomnessCollectiveFlip`.
+impl pallet_commit_reveal::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Slashed = ();
+	type CommitDeposit = CommitDeposit;
+	type RoundLength = RandomnessRoundLength;
+	type CommitPeriod = RandomnessCommitPeriod;
+	type MaxParticipants = MaxRandomnessParticipants;
+}
+
+parameter_types! {
+	pub const TemplateMaxNameLength: u32 = 32;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
+	type MaxNameLength = TemplateMaxNameLength;
+	type OracleKey = OracleKey;
+	type Oracle = Oracle;
+}
+
+parameter_types! {
+	pub const MaxTemplateWriters: u32 = 32;
+}
+
+/// The accounts whose `pallet_template` writes are given priority in the transaction pool.
+type TemplateWritersInstance = pallet_membership::Instance1;
+impl pallet_membership::Config<TemplateWritersInstance> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRoot<AccountId>;
+	type RemoveOrigin = EnsureRoot<AccountId>;
+	type SwapOrigin = EnsureRoot<AccountId>;
+	type ResetOrigin = EnsureRoot<AccountId>;
+	type PrimeOrigin = EnsureRoot<AccountId>;
+	type MembershipInitialized = ();
+	type MembershipChanged = ();
+	type MaxMembers = MaxTemplateWriters;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const RegistryEntryDeposit: Balance = 1_000_000;
+	pub const RegistryByteDeposit: Balance = 10_000;
+	pub const RegistryMaxNamespaceLength: u32 = 32;
+	pub const RegistryMaxKeyLength: u32 = 64;
+	pub const RegistryMaxValueLength: u32 = 256;
+}
+
+/// Configure the key-value registry in pallets/registry.
+impl pallet_registry::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type EntryDeposit = RegistryEntryDeposit;
+	type ByteDeposit = RegistryByteDeposit;
+	type MaxNamespaceLength = RegistryMaxNamespaceLength;
+	type MaxKeyLength = RegistryMaxKeyLength;
+	type MaxValueLength = RegistryMaxValueLength;
+}
+
+parameter_types! {
+	pub const FaucetPalletId: PalletId = PalletId(*b"py/fauct");
+	pub const FaucetClaimAmount: Balance = 1 << 50;
+	pub const FaucetClaimPeriod: BlockNumber = HOURS;
+	// Claims pay no fees, so they never take precedence over other transactions.
+	pub const FaucetUnsignedPriority: TransactionPriority = 0;
+	pub const FaucetPowDifficulty: u32 = 16;
+	pub const FaucetMaxClaimsPerBlock: u32 = 4;
+}
+
+/// Configure the development faucet in pallets/faucet.
+impl pallet_faucet::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = FaucetPalletId;
+	type ClaimAmount = FaucetClaimAmount;
+	type ClaimPeriod = FaucetClaimPeriod;
+	type UnsignedPriority = FaucetUnsignedPriority;
+	type PowDifficulty = FaucetPowDifficulty;
+	type MaxClaimsPerBlock = FaucetMaxClaimsPerBlock;
+}
+
+/// Configure conditional payments in pallets/escrow; disputes are settled by root.
+impl pallet_escrow::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ArbitratorOrigin = EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+	pub const PoeClaimDeposit: Balance = 1_000_000;
+	pub const PoeMaxMetadataLength: u32 = 128;
+}
+
+/// Configure document notarization in pallets/poe.
+impl pallet_poe::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ClaimDeposit = PoeClaimDeposit;
+	type MaxMetadataLength = PoeMaxMetadataLength;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -296,6 +730,26 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		// New pallets go at the end, so that the indices of the existing ones stay the same.
+		Registry: pallet_registry,
+		Faucet: pallet_faucet,
+		Escrow: pallet_escrow,
+		Poe: pallet_poe,
+		Assets: pallet_assets,
+		AssetTxPayment: pallet_asset_tx_payment,
+		Uniques: pallet_uniques,
+		Contracts: pallet_contracts,
+		Identity: pallet_identity,
+		IdentityRegistrars: pallet_identity_registrars::{Pallet, Config<T>},
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Oracle: pallet_oracle,
+		CommitReveal: pallet_commit_reveal,
+		Babe: pallet_babe,
+		DisabledValidators: pallet_disabled_validators,
+		NodeAuthorization: pallet_node_authorization,
+		TemplateWriters: pallet_membership::<Instance1>,
+		RateLimit: pallet_rate_limit,
 	}
 );
 
@@ -313,7 +767,9 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+	priority::PrioritizeCalls,
+	pallet_rate_limit::CheckRateLimit<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -394,6 +850,48 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_consensus_babe::BabeApi<Block> for Runtime {
+		fn configuration() -> sp_consensus_babe::BabeGenesisConfiguration {
+			sp_consensus_babe::BabeGenesisConfiguration {
+				slot_duration: Babe::slot_duration(),
+				epoch_length: EpochDuration::get(),
+				c: BABE_GENESIS_EPOCH_CONFIG.c,
+				genesis_authorities: Babe::authorities().to_vec(),
+				randomness: Babe::randomness(),
+				allowed_slots: BABE_GENESIS_EPOCH_CONFIG.allowed_slots,
+			}
+		}
+
+		fn current_epoch_start() -> sp_consensus_babe::Slot {
+			Babe::current_epoch_start()
+		}
+
+		fn current_epoch() -> sp_consensus_babe::Epoch {
+			Babe::current_epoch()
+		}
+
+		fn next_epoch() -> sp_consensus_babe::Epoch {
+			Babe::next_epoch()
+		}
+
+		fn generate_key_ownership_proof(
+			_slot: sp_consensus_babe::Slot,
+			_authority_id: sp_consensus_babe::AuthorityId,
+		) -> Option<sp_consensus_babe::OpaqueKeyOwnershipProof> {
+			// NOTE: this is the only implementation possible since we've
+			// defined our key owner proof type as a bottom type (i.e. a type
+			// with no values).
+			None
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_babe::EquivocationProof<<Block as BlockT>::Header>,
+			_key_owner_proof: sp_consensus_babe::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			opaque::SessionKeys::generate(seed)
@@ -457,6 +955,51 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_rpc_runtime_api::PoeApi<Block, AccountId, Hash, BlockNumber> for Runtime {
+		fn claims_of(owner: AccountId) -> Vec<(Hash, BlockNumber)> {
+			Poe::claims_of(&owner)
+		}
+	}
+
+	impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>
+		for Runtime
+	{
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			input_data: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractExecResult {
+			Contracts::bare_call(origin, dest, value, gas_limit, input_data, true)
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			endowment: Balance,
+			gas_limit: u64,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId> {
+			Contracts::bare_instantiate(origin, endowment, gas_limit, code, data, salt, true)
+		}
+
+		fn get_storage(
+			address: AccountId,
+			key: [u8; 32],
+		) -> pallet_contracts_primitives::GetStorageResult {
+			Contracts::get_storage(address, key)
+		}
+	}
+
+	impl pallet_uniques_rpc_runtime_api::UniquesApi<Block, AccountId, ClassId, InstanceId> for Runtime {
+		fn owned(who: AccountId) -> Vec<(ClassId, InstanceId)> {
+			use frame_support::traits::tokens::nonfungibles::InspectEnumerable;
+			Uniques::owned(&who).collect()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -473,8 +1016,21 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, frame_benchmarking, BaselineBench::<Runtime>);
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
+			list_benchmark!(list, extra, pallet_contracts, Contracts);
+			list_benchmark!(list, extra, pallet_identity, Identity);
+			list_benchmark!(list, extra, pallet_preimage, Preimage);
+			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
+			list_benchmark!(list, extra, pallet_assets, Assets);
+			list_benchmark!(list, extra, pallet_uniques, Uniques);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_registry, Registry);
+			list_benchmark!(list, extra, pallet_faucet, Faucet);
+			list_benchmark!(list, extra, pallet_escrow, Escrow);
+			list_benchmark!(list, extra, pallet_poe, Poe);
+			list_benchmark!(list, extra, pallet_oracle, Oracle);
+			list_benchmark!(list, extra, pallet_commit_reveal, CommitReveal);
+			list_benchmark!(list, extra, pallet_disabled_validators, DisabledValidators);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -511,8 +1067,21 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_benchmarking, BaselineBench::<Runtime>);
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
+			add_benchmark!(params, batches, pallet_contracts, Contracts);
+			add_benchmark!(params, batches, pallet_identity, Identity);
+			add_benchmark!(params, batches, pallet_preimage, Preimage);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
+			add_benchmark!(params, batches, pallet_assets, Assets);
+			add_benchmark!(params, batches, pallet_uniques, Uniques);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_registry, Registry);
+			add_benchmark!(params, batches, pallet_faucet, Faucet);
+			add_benchmark!(params, batches, pallet_escrow, Escrow);
+			add_benchmark!(params, batches, pallet_poe, Poe);
+			add_benchmark!(params, batches, pallet_oracle, Oracle);
+			add_benchmark!(params, batches, pallet_commit_reveal, CommitReveal);
+			add_benchmark!(params, batches, pallet_disabled_validators, DisabledValidators);
 
 			Ok(batches)
 		}
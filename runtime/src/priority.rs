@@ -0,0 +1,97 @@
+//! Priority lanes for the transaction pool.
+//!
+//! [`PrioritizeCalls`] raises operational calls signed by the sudo key, and `pallet_template` writes
+//! signed by an account of the root-managed `TemplateWriters` set, above ordinary transactions
+//! whatever their tip. Operational calls of other accounts are not boosted, so that wrapping a call
+//! in `sudo` does not jump the pool. The boosts are added to the priority the other signed extensions compute, so tips still order
+//! transactions within a lane.
+
+use crate::{AccountId, Call, SudoKey, TemplateWriters};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::Contains,
+	weights::{DispatchClass, DispatchInfo},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+
+/// Priority added to operational transactions of the sudo key, such as sudo calls of operational
+/// dispatchables.
+pub const OPERATIONAL_PRIORITY_BOOST: TransactionPriority = TransactionPriority::max_value() / 4;
+
+/// Priority added to `pallet_template` writes signed by a member of `TemplateWriters`.
+pub const WRITER_PRIORITY_BOOST: TransactionPriority = TransactionPriority::max_value() / 8;
+
+/// Boosts the pool priority of the sudo key's operational calls and of `pallet_template` writes by
+/// the `TemplateWriters`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct PrioritizeCalls;
+
+impl PrioritizeCalls {
+	/// The priority added to `call`, signed by `who`.
+	pub fn boost(who: &AccountId, call: &Call, info: &DispatchInfo) -> TransactionPriority {
+		let mut boost: TransactionPriority = 0;
+		if info.class == DispatchClass::Operational && SudoKey::contains(who) {
+			boost = boost.saturating_add(OPERATIONAL_PRIORITY_BOOST);
+		}
+		if is_template_write(call) && TemplateWriters::contains(who) {
+			boost = boost.saturating_add(WRITER_PRIORITY_BOOST);
+		}
+		boost
+	}
+}
+
+/// Whether `call` writes the `pallet_template` record.
+fn is_template_write(call: &Call) -> bool {
+	use pallet_template::Call as TemplateCall;
+
+	matches!(
+		call,
+		Call::TemplateModule(
+			TemplateCall::do_something { .. } |
+				TemplateCall::update_something { .. } |
+				TemplateCall::increment_something { .. } |
+				TemplateCall::decrement_something { .. } |
+				TemplateCall::update_from_oracle { .. }
+		)
+	)
+}
+
+impl SignedExtension for PrioritizeCalls {
+	const IDENTIFIER: &'static str = "PrioritizeCalls";
+	type AccountId = AccountId;
+	type Call = Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		Ok(ValidTransaction { priority: Self::boost(who, call, info), ..Default::default() })
+	}
+
+	// Priority only matters to the pool, so there is nothing to check when the block is built.
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(())
+	}
+}
@@ -0,0 +1,534 @@
+use crate::{
+	chain_extension::{READ_SOMETHING, STORE_SOMETHING},
+	priority::{PrioritizeCalls, OPERATIONAL_PRIORITY_BOOST, WRITER_PRIORITY_BOOST},
+	AccountId, AssetDeposit, Assets, Aura, Balance, Balances, BasicDeposit, Block, BlockNumber,
+	Call, ClassId, Contracts, DisabledValidators, Executive, ExistentialDeposit, Header, Identity,
+	InstanceId, MaxAdditionalFields, MaxTransactionsPerWindow, MetadataDepositBase,
+	MetadataDepositPerByte, NodeAuthorization, Origin, OriginCaller, Runtime, Scheduler, System,
+	TemplateModule, TemplateWriters, TransactionPayment, Uniques,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	error::BadOrigin,
+	traits::{
+		schedule::{Anon, DispatchTime, LOWEST_PRIORITY},
+		Get, OnInitialize,
+	},
+	weights::{DispatchClass, GetDispatchInfo, Pays, PostDispatchInfo, Weight},
+};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use pallet_contracts_primitives::{Code, ExecReturnValue};
+use pallet_identity::{Data, IdentityInfo, Judgement};
+use pallet_template::Value;
+use pallet_uniques_rpc_runtime_api::runtime_decl_for_UniquesApi;
+use sp_consensus_aura::{sr25519::AuthorityId as AuraId, Slot, AURA_ENGINE_ID};
+use sp_core::{sr25519, OpaquePeerId};
+use sp_runtime::{
+	generic::DigestItem,
+	traits::{Header as _, SignedExtension},
+	BuildStorage, FixedPointNumber,
+};
+
+const GAS_LIMIT: Weight = 100_000_000_000;
+const ENDOWMENT: Balance = 10_000_000_000;
+const AUTHORITIES: u64 = 2;
+
+fn alice() -> AccountId {
+	AccountId::from([1u8; 32])
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let t = crate::GenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(alice(), 1 << 60)] },
+		aura: pallet_aura::GenesisConfig {
+			authorities: (0..AUTHORITIES)
+				.map(|i| AuraId::from(sr25519::Public::from_raw([i as u8; 32])))
+				.collect(),
+		},
+		babe: pallet_babe::GenesisConfig {
+			authorities: vec![],
+			epoch_config: Some(crate::BABE_GENESIS_EPOCH_CONFIG),
+		},
+		sudo: pallet_sudo::GenesisConfig { key: alice() },
+		identity_registrars: pallet_identity_registrars::GenesisConfig {
+			registrars: vec![alice()],
+		},
+		template_writers: pallet_membership::GenesisConfig {
+			members: vec![alice()],
+			phantom: Default::default(),
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn instantiate_fixture() -> AccountId {
+	let wasm =
+		wat::parse_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/template_extension.wat"))
+			.expect("fixture is valid wat; qed");
+	Contracts::bare_instantiate(
+		alice(),
+		ENDOWMENT,
+		GAS_LIMIT,
+		Code::Upload(wasm.into()),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.expect("fixture deploys; qed")
+	.account_id
+}
+
+fn call_extension(contract: &AccountId, func_id: u32, input: Vec<u8>) -> ExecReturnValue {
+	let data = func_id.to_le_bytes().iter().copied().chain(input).collect();
+	Contracts::bare_call(alice(), contract.clone(), 0, GAS_LIMIT, data, false)
+		.result
+		.unwrap()
+}
+
+#[test]
+fn contract_reads_and_writes_template_storage() {
+	new_test_ext().execute_with(|| {
+		let contract = instantiate_fixture();
+
+		let empty = call_extension(&contract, READ_SOMETHING, vec![]);
+		assert_eq!(Option::<Value>::decode(&mut &empty.data[..]).unwrap(), None);
+
+		let value = Value::saturating_from_integer(42);
+		call_extension(&contract, STORE_SOMETHING, (b"answer".to_vec(), value).encode());
+		let record = TemplateModule::something().unwrap();
+		assert_eq!(record.owner, contract);
+		assert_eq!(record.value, value);
+
+		let stored = call_extension(&contract, READ_SOMETHING, vec![]);
+		assert_eq!(Option::<Value>::decode(&mut &stored.data[..]).unwrap(), Some(value));
+	});
+}
+
+#[test]
+fn contract_cannot_overwrite_foreign_record() {
+	new_test_ext().execute_with(|| {
+		let value = Value::saturating_from_integer(1);
+		assert_ok!(TemplateModule::do_something(Origin::signed(alice()), b"mine".to_vec(), value));
+
+		let contract = instantiate_fixture();
+		let data = STORE_SOMETHING
+			.to_le_bytes()
+			.iter()
+			.copied()
+			.chain((b"theirs".to_vec(), Value::saturating_from_integer(2)).encode())
+			.collect();
+		assert!(Contracts::bare_call(alice(), contract, 0, GAS_LIMIT, data, false)
+			.result
+			.is_err());
+		assert_eq!(TemplateModule::something().unwrap().value, value);
+	});
+}
+
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Scheduler::on_initialize(System::block_number());
+	}
+}
+
+fn schedule_as_alice(
+	when: BlockNumber,
+	maybe_periodic: Option<(BlockNumber, u32)>,
+	call: pallet_template::Call<crate::Runtime>,
+) {
+	let origin: OriginCaller = frame_system::RawOrigin::Signed(alice()).into();
+	assert_ok!(<Scheduler as Anon<_, _, _>>::schedule(
+		DispatchTime::At(when),
+		maybe_periodic,
+		LOWEST_PRIORITY,
+		origin,
+		Call::TemplateModule(call),
+	));
+}
+
+#[test]
+fn scheduled_template_call_fires_at_block() {
+	new_test_ext().execute_with(|| {
+		let value = Value::saturating_from_integer(7);
+		schedule_as_alice(
+			4,
+			None,
+			pallet_template::Call::do_something { name: b"later".to_vec(), value },
+		);
+
+		run_to_block(3);
+		assert_eq!(TemplateModule::something(), None);
+
+		run_to_block(4);
+		let record = TemplateModule::something().unwrap();
+		assert_eq!(record.owner, alice());
+		assert_eq!(record.value, value);
+		assert_eq!(record.updated_at, 4);
+	});
+}
+
+#[test]
+fn periodic_template_call_fires_every_period() {
+	new_test_ext().execute_with(|| {
+		let one = Value::saturating_from_integer(1);
+		assert_ok!(TemplateModule::do_something(Origin::signed(alice()), b"tick".to_vec(), one));
+		schedule_as_alice(
+			2,
+			Some((2, 3)),
+			pallet_template::Call::increment_something { amount: one },
+		);
+
+		run_to_block(3);
+		assert_eq!(TemplateModule::something().unwrap().value, Value::saturating_from_integer(2));
+
+		run_to_block(6);
+		assert_eq!(TemplateModule::something().unwrap().value, Value::saturating_from_integer(4));
+
+		// The period has been used up.
+		run_to_block(10);
+		assert_eq!(TemplateModule::something().unwrap().value, Value::saturating_from_integer(4));
+	});
+}
+
+/// Initialize the block following the current one as authored in `slot`, whose author is the
+/// Aura authority at index `slot % AUTHORITIES`.
+fn import_block_at_slot(slot: u64) {
+	let mut header = Header::new(
+		System::block_number() + 1,
+		Default::default(),
+		Default::default(),
+		System::parent_hash(),
+		Default::default(),
+	);
+	header
+		.digest_mut()
+		.push(DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(slot).encode()));
+	// Aura rejects blocks of disabled authorities when initializing them.
+	Executive::initialize_block(&header);
+}
+
+#[test]
+fn enabled_authorities_author_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DisabledValidators::disable(Origin::root(), 0));
+		import_block_at_slot(1);
+		import_block_at_slot(3);
+		assert_eq!(Aura::current_slot(), Slot::from(3));
+	});
+}
+
+#[test]
+#[should_panic(expected = "is disabled")]
+fn disabled_authority_block_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DisabledValidators::disable(Origin::root(), 0));
+		import_block_at_slot(1);
+		import_block_at_slot(2);
+	});
+}
+
+#[test]
+fn reenabled_authority_authors_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DisabledValidators::disable(Origin::root(), 0));
+		assert_ok!(DisabledValidators::enable(Origin::root(), 0));
+		import_block_at_slot(2);
+		assert_eq!(Aura::current_slot(), Slot::from(2));
+	});
+}
+
+#[test]
+fn disabling_is_bounded_by_the_aura_authority_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			DisabledValidators::disable(Origin::root(), AUTHORITIES as u32),
+			pallet_disabled_validators::Error::<Runtime>::InvalidIndex
+		);
+		assert_ok!(DisabledValidators::disable(Origin::root(), 0));
+		assert_noop!(
+			DisabledValidators::disable(Origin::root(), 1),
+			pallet_disabled_validators::Error::<Runtime>::LastEnabled
+		);
+	});
+}
+
+#[test]
+fn only_root_manages_well_known_nodes() {
+	new_test_ext().execute_with(|| {
+		let node = OpaquePeerId(vec![1; 38]);
+		assert_noop!(
+			NodeAuthorization::add_well_known_node(Origin::signed(alice()), node.clone(), alice()),
+			BadOrigin
+		);
+
+		assert_ok!(NodeAuthorization::add_well_known_node(Origin::root(), node.clone(), alice()));
+		assert!(NodeAuthorization::well_known_nodes().contains(&node));
+		assert_eq!(NodeAuthorization::owners(&node), Some(alice()));
+
+		assert_ok!(NodeAuthorization::remove_well_known_node(Origin::root(), node.clone()));
+		assert!(!NodeAuthorization::well_known_nodes().contains(&node));
+	});
+}
+
+fn priority_of(who: &AccountId, call: Call) -> u64 {
+	let info = call.get_dispatch_info();
+	PrioritizeCalls.validate(who, &call, &info, 0).unwrap().priority
+}
+
+#[test]
+fn ordinary_calls_get_no_priority_boost() {
+	new_test_ext().execute_with(|| {
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		assert_eq!(priority_of(&alice(), remark), 0);
+	});
+}
+
+#[test]
+fn operational_calls_are_boosted() {
+	new_test_ext().execute_with(|| {
+		let set_heap_pages = Call::System(frame_system::Call::set_heap_pages { pages: 64 });
+		let sudo = Call::Sudo(pallet_sudo::Call::sudo { call: Box::new(set_heap_pages) });
+		assert_eq!(priority_of(&alice(), sudo), OPERATIONAL_PRIORITY_BOOST);
+	});
+}
+
+#[test]
+fn operational_calls_of_other_accounts_are_not_boosted() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		let set_heap_pages = Call::System(frame_system::Call::set_heap_pages { pages: 64 });
+		let sudo = Call::Sudo(pallet_sudo::Call::sudo { call: Box::new(set_heap_pages) });
+		assert_eq!(sudo.get_dispatch_info().class, DispatchClass::Operational);
+		assert_eq!(priority_of(&bob, sudo), 0);
+	});
+}
+
+#[test]
+fn template_writes_by_writers_are_boosted() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		let update = || {
+			Call::TemplateModule(pallet_template::Call::update_something {
+				value: Value::saturating_from_integer(2),
+			})
+		};
+		assert_eq!(priority_of(&alice(), update()), WRITER_PRIORITY_BOOST);
+		assert_eq!(priority_of(&bob, update()), 0);
+
+		assert_ok!(TemplateWriters::add_member(Origin::root(), bob.clone()));
+		assert_eq!(priority_of(&bob, update()), WRITER_PRIORITY_BOOST);
+	});
+}
+
+#[test]
+fn only_template_writes_are_boosted() {
+	new_test_ext().execute_with(|| {
+		let cause_error = Call::TemplateModule(pallet_template::Call::cause_error {});
+		assert_eq!(priority_of(&alice(), cause_error), 0);
+	});
+}
+
+#[test]
+fn only_root_manages_template_writers() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		assert_noop!(TemplateWriters::add_member(Origin::signed(alice()), bob), BadOrigin);
+
+		assert_ok!(TemplateWriters::remove_member(Origin::root(), alice()));
+		let update = Call::TemplateModule(pallet_template::Call::update_something {
+			value: Value::saturating_from_integer(2),
+		});
+		assert_eq!(priority_of(&alice(), update), 0);
+	});
+}
+
+#[test]
+fn prioritize_calls_pre_dispatch_accepts_everything() {
+	new_test_ext().execute_with(|| {
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let info = remark.get_dispatch_info();
+		assert_ok!(PrioritizeCalls.pre_dispatch(&alice(), &remark, &info, 0));
+	});
+}
+
+#[test]
+fn sudo_key_is_not_rate_limited() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let info = remark.get_dispatch_info();
+		let check = pallet_rate_limit::CheckRateLimit::<Runtime>::new;
+
+		for _ in 0..MaxTransactionsPerWindow::get() {
+			assert_ok!(check().pre_dispatch(&alice(), &remark, &info, 0));
+			assert_ok!(check().pre_dispatch(&bob, &remark, &info, 0));
+		}
+		assert_ok!(check().validate(&alice(), &remark, &info, 0));
+		assert!(check().validate(&bob, &remark, &info, 0).is_err());
+	});
+}
+
+const ASSET: u32 = 1;
+
+#[test]
+fn only_root_force_creates_assets_without_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Assets::force_create(Origin::signed(alice()), ASSET, alice().into(), true, 1),
+			BadOrigin
+		);
+		assert_ok!(Assets::force_create(Origin::root(), ASSET, alice().into(), true, 1));
+		assert_eq!(Balances::reserved_balance(alice()), 0);
+	});
+}
+
+#[test]
+fn asset_creation_and_metadata_take_deposits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(alice()), ASSET, alice().into(), 1));
+		assert_eq!(Balances::reserved_balance(alice()), AssetDeposit::get());
+
+		assert_ok!(Assets::set_metadata(
+			Origin::signed(alice()),
+			ASSET,
+			b"Token".to_vec(),
+			b"TKN".to_vec(),
+			10
+		));
+		assert_eq!(
+			Balances::reserved_balance(alice()),
+			AssetDeposit::get() + MetadataDepositBase::get() + MetadataDepositPerByte::get() * 8
+		);
+	});
+}
+
+#[test]
+fn fees_can_be_paid_in_a_sufficient_asset() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		// A minimum balance equal to the existential deposit converts fees one to one.
+		let min_balance = ExistentialDeposit::get();
+		assert_ok!(Assets::force_create(Origin::root(), ASSET, alice().into(), true, min_balance));
+		assert_ok!(Assets::mint(Origin::signed(alice()), ASSET, bob.clone().into(), 1 << 40));
+
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let info = remark.get_dispatch_info();
+		let len = remark.encoded_size();
+		let fee = TransactionPayment::compute_fee(len as u32, &info, 0);
+		assert!(fee > 0);
+
+		let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+			.pre_dispatch(&bob, &remark, &info, len)
+			.unwrap();
+		assert_eq!(Assets::balance(ASSET, &bob), (1 << 40) - fee);
+		assert_eq!(Balances::free_balance(&bob), 0);
+
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			pre,
+			&info,
+			&PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes },
+			len,
+			&Ok(())
+		));
+		assert_eq!(Assets::balance(ASSET, &bob), (1 << 40) - fee);
+	});
+}
+
+#[test]
+fn fees_cannot_be_paid_in_an_insufficient_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), ASSET, alice().into(), false, 1));
+		assert_ok!(Assets::mint(Origin::signed(alice()), ASSET, alice().into(), 1 << 40));
+
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let info = remark.get_dispatch_info();
+		assert!(ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+			.pre_dispatch(&alice(), &remark, &info, 0)
+			.is_err());
+	});
+}
+
+fn owned(who: &AccountId) -> Vec<(ClassId, InstanceId)> {
+	let mut items = <Runtime as runtime_decl_for_UniquesApi::UniquesApi<
+		Block,
+		AccountId,
+		ClassId,
+		InstanceId,
+	>>::owned(who.clone());
+	items.sort();
+	items
+}
+
+#[test]
+fn owned_api_lists_minted_uniques() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		assert_ok!(Uniques::force_create(Origin::root(), 0, alice().into(), true));
+		assert_ok!(Uniques::force_create(Origin::root(), 1, alice().into(), true));
+		assert_eq!(owned(&bob), vec![]);
+
+		assert_ok!(Uniques::mint(Origin::signed(alice()), 0, 7, bob.clone().into()));
+		assert_ok!(Uniques::mint(Origin::signed(alice()), 1, 3, bob.clone().into()));
+		assert_ok!(Uniques::mint(Origin::signed(alice()), 1, 4, alice().into()));
+		assert_eq!(owned(&bob), vec![(0, 7), (1, 3)]);
+		assert_eq!(owned(&alice()), vec![(1, 4)]);
+
+		assert_ok!(Uniques::transfer(Origin::signed(bob.clone()), 0, 7, alice().into()));
+		assert_eq!(owned(&bob), vec![(1, 3)]);
+		assert_eq!(owned(&alice()), vec![(0, 7), (1, 4)]);
+	});
+}
+
+fn identity_named(name: &[u8]) -> Box<IdentityInfo<MaxAdditionalFields>> {
+	let raw = |bytes: &[u8]| Data::Raw(bytes.to_vec().try_into().unwrap());
+	Box::new(IdentityInfo {
+		additional: Default::default(),
+		display: raw(name),
+		legal: Data::None,
+		web: Data::None,
+		riot: Data::None,
+		email: raw(b"bob@example.com"),
+		pgp_fingerprint: None,
+		image: Data::None,
+		twitter: Data::None,
+	})
+}
+
+#[test]
+fn genesis_registrar_judges_identities() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		assert_ok!(Balances::transfer(Origin::signed(alice()), bob.clone().into(), ENDOWMENT));
+		assert_eq!(Identity::registrars()[0].as_ref().map(|r| r.account.clone()), Some(alice()));
+
+		assert_ok!(Identity::set_identity(Origin::signed(bob.clone()), identity_named(b"bob")));
+		assert_eq!(Balances::reserved_balance(&bob), BasicDeposit::get());
+
+		assert_ok!(Identity::request_judgement(Origin::signed(bob.clone()), 0, 0));
+		assert_ok!(Identity::provide_judgement(
+			Origin::signed(alice()),
+			0,
+			bob.clone().into(),
+			Judgement::Reasonable
+		));
+		let judgements = Identity::identity(&bob).unwrap().judgements;
+		assert_eq!(judgements.into_inner(), vec![(0, Judgement::Reasonable)]);
+	});
+}
+
+#[test]
+fn only_root_adds_registrars() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountId::from([2u8; 32]);
+		assert_noop!(Identity::add_registrar(Origin::signed(alice()), bob.clone()), BadOrigin);
+		assert_ok!(Identity::add_registrar(Origin::root(), bob.clone()));
+		assert_eq!(Identity::registrars()[1].as_ref().map(|r| r.account.clone()), Some(bob));
+	});
+}